@@ -0,0 +1,200 @@
+//! A global allocator that counts allocations, for profiling how much memory
+//! each phase of a solution uses.
+//!
+//! A binary opts in by installing it:
+//!
+//! ```no_run
+//! #[global_allocator]
+//! static ALLOCATOR: aoc_core::alloc::CountingAllocator = aoc_core::alloc::CountingAllocator;
+//! # fn main() {}
+//! ```
+//!
+//! and calling [`enable`], after which every [`Report`](crate::Report) carries
+//! the allocations of each phase. Until then nothing is counted, so that the
+//! allocator costs no more than the system one. The counters are shared by all
+//! threads, so phases must not run concurrently while profiling.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use serde::Serialize;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Forwards to the system allocator, counting what it allocates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingAllocator;
+
+fn grow(size: usize) {
+    if !is_enabled() {
+        return;
+    }
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn shrink(size: usize) {
+    if !is_enabled() {
+        return;
+    }
+    // memory allocated before counting started may be freed after it
+    let _ = CURRENT.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        Some(current.saturating_sub(size))
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    // a reallocation counts as allocating the new size and freeing the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            grow(new_size);
+            shrink(layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Makes the reports record allocations, which needs the [`CountingAllocator`]
+/// to be installed. Only allocations made from then on are counted.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// What was allocated while running a phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: usize,
+    /// Total bytes allocated, even if freed again.
+    pub bytes: usize,
+    /// Most bytes held at once on top of those held before the phase.
+    pub peak: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocation{}, {} allocated, {} peak",
+            self.allocations,
+            if self.allocations == 1 { "" } else { "s" },
+            ByteSize(self.bytes),
+            ByteSize(self.peak)
+        )
+    }
+}
+
+struct ByteSize(usize);
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Runs `f`, counting its allocations if profiling is enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(current),
+    };
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_measure() {
+        enable();
+
+        let (len, stats) = measure(|| {
+            let big = vec![1u8; 1 << 20];
+            let mut small = Vec::new();
+            for x in 0..100u32 {
+                small.push(x);
+            }
+            big.len() + small.len()
+        });
+        let stats = stats.unwrap();
+        assert_eq!(len, (1 << 20) + 100);
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= (1 << 20) + 400);
+        assert!(stats.peak >= 1 << 20);
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 1536,
+            peak: 1000,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 1.5 KiB allocated, 1000 B peak"
+        );
+        assert_eq!(ByteSize(5 << 30).to_string(), "5.0 GiB");
+    }
+}
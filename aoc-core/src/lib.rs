@@ -0,0 +1,507 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufRead, BufReader, IsTerminal, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use color_eyre::{eyre::eyre, Report as Error, Result};
+use serde::Serialize;
+use tracing::{info_span, Span};
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+pub mod alloc;
+mod location;
+
+use alloc::AllocStats;
+pub use location::{for_each_line, lines, locate, Line, Location};
+
+/// A single day's puzzle, split into a parsing phase and the two parts.
+pub trait Solution {
+    /// The day of December the puzzle was released on.
+    const DAY: u8;
+
+    /// Parsed puzzle input, shared by both parts.
+    type Input;
+    type Answer1: Display + Into<Value>;
+    type Answer2: Display + Into<Value>;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+/// A [`Solution`] that can also be solved while reading its input, keeping only
+/// a bounded part of it in memory.
+pub trait StreamingSolution: Solution {
+    fn solve_reader(reader: &mut dyn BufRead) -> Result<(Self::Answer1, Self::Answer2)>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(eyre!("invalid part: {}", s)),
+        }
+    }
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "part1"),
+            Part::Two => write!(f, "part2"),
+        }
+    }
+}
+
+/// The answer to a part, keeping track of whether it is a number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Value {
+    /// Wide enough for any `usize` or `isize`.
+    Number(i128),
+    Text(String),
+}
+
+impl From<usize> for Value {
+    fn from(x: usize) -> Self {
+        Value::Number(x as i128)
+    }
+}
+
+impl From<isize> for Value {
+    fn from(x: isize) -> Self {
+        Value::Number(x as i128)
+    }
+}
+
+impl From<String> for Value {
+    fn from(x: String) -> Self {
+        Value::Text(x)
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Number(x) => write!(f, "{}", x),
+            Value::Text(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: Part,
+    pub value: Value,
+    pub time: Duration,
+    /// What solving the part allocated, if profiling.
+    pub alloc: Option<AllocStats>,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.value.to_string();
+        // multi-line answers (e.g. rendered letters) start on their own line
+        if value.contains('\n') {
+            write!(f, "Solution to {}: \n{}", self.part, value)
+        } else {
+            write!(f, "Solution to {}: {}", self.part, value)
+        }
+    }
+}
+
+/// The answers of one run of a day, with the time spent in each phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u8,
+    /// Where the input came from, if known.
+    pub input: Option<String>,
+    pub parse_time: Duration,
+    /// What parsing allocated, if profiling.
+    pub parse_alloc: Option<AllocStats>,
+    pub answers: Vec<Answer>,
+}
+
+#[derive(Serialize)]
+struct JsonAnswer<'a> {
+    day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<&'a str>,
+    part: u8,
+    answer: &'a Value,
+    parse_time_ms: f64,
+    solve_time_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parse_alloc: Option<AllocStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solve_alloc: Option<AllocStats>,
+}
+
+impl Report {
+    /// One JSON object per answer, each on its own line.
+    pub fn to_json_lines(&self) -> String {
+        self.answers
+            .iter()
+            .map(|answer| {
+                serde_json::to_string(&JsonAnswer {
+                    day: self.day,
+                    input: self.input.as_deref(),
+                    part: answer.part.number(),
+                    answer: &answer.value,
+                    parse_time_ms: self.parse_time.as_secs_f64() * 1000.0,
+                    solve_time_ms: answer.time.as_secs_f64() * 1000.0,
+                    parse_alloc: self.parse_alloc,
+                    solve_alloc: answer.alloc,
+                })
+                .expect("answers serialize to JSON")
+                    + "\n"
+            })
+            .collect()
+    }
+
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => {
+                for answer in &self.answers {
+                    println!("{}", answer);
+                }
+                if let Some(alloc) = self.parse_alloc {
+                    println!("Allocations in parse: {}", alloc);
+                }
+                for answer in &self.answers {
+                    if let Some(alloc) = answer.alloc {
+                        println!("Allocations in {}: {}", answer.part, alloc);
+                    }
+                }
+            }
+            Format::Json => print!("{}", self.to_json_lines()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(eyre!("invalid format: {}", s)),
+        }
+    }
+}
+
+/// Logs to stderr what `RUST_LOG` asks for, or else depending on `verbosity`,
+/// the number of `-v` flags: only warnings, then also the spans and debug
+/// events of the solvers, then everything.
+pub fn init_tracing(verbosity: u8) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        // directives match targets by prefix, so `day` covers every day
+        EnvFilter::new(match verbosity {
+            0 => "warn",
+            1 => "warn,aoc=debug,day=debug",
+            _ => "trace",
+        })
+    });
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_span_events(FmtSpan::CLOSE)
+        .init();
+}
+
+// runs a phase within `span`, timing it and counting its allocations if profiling
+fn timed<T>(span: Span, f: impl FnOnce() -> T) -> (T, Duration, Option<AllocStats>) {
+    let start = Instant::now();
+    let (result, alloc) = span.in_scope(|| alloc::measure(f));
+    (result, start.elapsed(), alloc)
+}
+
+/// Parses `input` and solves the requested part, or both parts if `part` is `None`.
+pub fn solve<S: Solution>(input: &str, part: Option<Part>) -> Result<Report> {
+    let _span = info_span!("solve", day = S::DAY).entered();
+    let (input, parse_time, parse_alloc) = timed(info_span!("parse"), || S::parse(input));
+    let input = input?;
+
+    let mut answers = Vec::new();
+    if part != Some(Part::Two) {
+        let (value, time, alloc) = timed(info_span!("part1"), || S::part1(&input));
+        answers.push(Answer {
+            part: Part::One,
+            value: value?.into(),
+            time,
+            alloc,
+        });
+    }
+    if part != Some(Part::One) {
+        let (value, time, alloc) = timed(info_span!("part2"), || S::part2(&input));
+        answers.push(Answer {
+            part: Part::Two,
+            value: value?.into(),
+            time,
+            alloc,
+        });
+    }
+
+    Ok(Report {
+        day: S::DAY,
+        input: None,
+        parse_time,
+        parse_alloc,
+        answers,
+    })
+}
+
+/// Solves both parts while reading the input from `reader`, keeping only the
+/// requested part if `part` is given. As parsing and solving are interleaved,
+/// all the time and allocations are counted as parsing.
+pub fn solve_reader<S: StreamingSolution>(
+    reader: &mut dyn BufRead,
+    part: Option<Part>,
+) -> Result<Report> {
+    let _span = info_span!("solve", day = S::DAY).entered();
+    let (answers, parse_time, parse_alloc) =
+        timed(info_span!("stream"), || S::solve_reader(reader));
+    let (answer1, answer2) = answers?;
+    let alloc = parse_alloc.map(|_| AllocStats::default());
+
+    let mut answers = Vec::new();
+    if part != Some(Part::Two) {
+        answers.push(Answer {
+            part: Part::One,
+            value: answer1.into(),
+            time: Duration::ZERO,
+            alloc,
+        });
+    }
+    if part != Some(Part::One) {
+        answers.push(Answer {
+            part: Part::Two,
+            value: answer2.into(),
+            time: Duration::ZERO,
+            alloc,
+        });
+    }
+
+    Ok(Report {
+        day: S::DAY,
+        input: None,
+        parse_time,
+        parse_alloc,
+        answers,
+    })
+}
+
+/// Opens the file at `path` for reading, or stdin if `path` is `-`.
+pub fn open_input(path: &Path) -> Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        Ok(Box::new(std::io::stdin().lock()))
+    } else {
+        let file = File::open(path).map_err(|e| eyre!("{}: {}", path.display(), e))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Reads the file at `path`, or stdin if `path` is `-`.
+pub fn read_input(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(path).map_err(|e| eyre!("{}: {}", path.display(), e))
+    }
+}
+
+fn input_name(path: &Path) -> String {
+    if path == Path::new("-") {
+        "<stdin>".to_owned()
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// Solves each of the input files with `solve` and prints the reports,
+/// headed by the name of the file if there is more than one.
+pub fn run_inputs(
+    paths: &[PathBuf],
+    format: Format,
+    solve: impl Fn(&str) -> Result<Report>,
+) -> Result<()> {
+    run_paths(paths, format, |path| solve(&read_input(path)?))
+}
+
+/// Like [`run_inputs`], but lets `solve` read each input as it goes.
+pub fn run_readers(
+    paths: &[PathBuf],
+    format: Format,
+    solve: impl Fn(&mut dyn BufRead) -> Result<Report>,
+) -> Result<()> {
+    run_paths(paths, format, |path| solve(&mut open_input(path)?))
+}
+
+fn run_paths(
+    paths: &[PathBuf],
+    format: Format,
+    solve: impl Fn(&Path) -> Result<Report>,
+) -> Result<()> {
+    if paths.iter().filter(|p| *p == Path::new("-")).count() > 1 {
+        return Err(eyre!("stdin can only be read once"));
+    }
+
+    for (idx, path) in paths.iter().enumerate() {
+        let mut report = solve(path)?;
+        report.input = Some(input_name(path));
+
+        if format == Format::Text && paths.len() > 1 {
+            if idx > 0 {
+                println!();
+            }
+            println!("==> {} <==", input_name(path));
+        }
+        report.print(format);
+    }
+
+    Ok(())
+}
+
+/// Solves both parts for each of the input files and prints the answers.
+pub fn run<S: Solution>(paths: &[PathBuf], format: Format) -> Result<()> {
+    run_inputs(paths, format, |input| solve::<S>(input, None))
+}
+
+type SolveReader = fn(&mut dyn BufRead, Option<Part>) -> Result<Report>;
+
+/// A type-erased [`Solution`], so that tools can drive any day uniformly.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    day: u8,
+    solve: fn(&str, Option<Part>) -> Result<Report>,
+    solve_reader: Option<SolveReader>,
+}
+
+impl Day {
+    pub fn new<S: Solution>() -> Self {
+        Day {
+            day: S::DAY,
+            solve: solve::<S>,
+            solve_reader: None,
+        }
+    }
+    /// A day that can also be solved while reading its input.
+    pub fn streaming<S: StreamingSolution>() -> Self {
+        Day {
+            solve_reader: Some(solve_reader::<S>),
+            ..Day::new::<S>()
+        }
+    }
+    pub fn number(&self) -> u8 {
+        self.day
+    }
+    pub fn solve(&self, input: &str, part: Option<Part>) -> Result<Report> {
+        (self.solve)(input, part)
+    }
+    pub fn solve_reader(&self, reader: &mut dyn BufRead, part: Option<Part>) -> Result<Report> {
+        let solve_reader = self
+            .solve_reader
+            .ok_or_else(|| eyre!("day {} can't be solved from a stream", self.day))?;
+        solve_reader(reader, part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines() {
+        let report = Report {
+            day: 10,
+            input: None,
+            parse_time: Duration::from_micros(1500),
+            parse_alloc: None,
+            answers: vec![
+                Answer {
+                    part: Part::One,
+                    value: 13140usize.into(),
+                    time: Duration::from_micros(250),
+                    alloc: None,
+                },
+                Answer {
+                    part: Part::Two,
+                    value: String::from("##\n #").into(),
+                    time: Duration::from_millis(2),
+                    alloc: None,
+                },
+            ],
+        };
+
+        assert_eq!(
+            report.to_json_lines(),
+            r###"{"day":10,"part":1,"answer":13140,"parse_time_ms":1.5,"solve_time_ms":0.25}
+{"day":10,"part":2,"answer":"##\n #","parse_time_ms":1.5,"solve_time_ms":2.0}
+"###
+        );
+
+        let stats = |allocations| AllocStats {
+            allocations,
+            bytes: 64 * allocations,
+            peak: 64,
+        };
+        let report = Report {
+            parse_alloc: Some(stats(3)),
+            answers: vec![Answer {
+                alloc: Some(stats(1)),
+                ..report.answers[0].clone()
+            }],
+            ..report
+        };
+        assert_eq!(
+            report.to_json_lines(),
+            r###"{"day":10,"part":1,"answer":13140,"parse_time_ms":1.5,"solve_time_ms":0.25,"parse_alloc":{"allocations":3,"bytes":192,"peak":64},"solve_alloc":{"allocations":1,"bytes":64,"peak":64}}
+"###
+        );
+
+        let report = Report {
+            parse_alloc: None,
+            answers: vec![Answer {
+                value: usize::MAX.into(),
+                alloc: None,
+                ..report.answers[0].clone()
+            }],
+            ..report
+        };
+        assert!(report
+            .to_json_lines()
+            .contains(&format!(r#""answer":{},"#, usize::MAX)));
+    }
+}
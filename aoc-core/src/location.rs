@@ -0,0 +1,203 @@
+use std::{fmt::Display, io::BufRead};
+
+/// A position in the puzzle input, for pointing at malformed parts of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The whole line containing the position.
+    pub text: String,
+}
+
+impl Location {
+    /// Location of `column` in `line`, whose whole text is `text`.
+    pub fn new(line: usize, column: usize, text: &str) -> Self {
+        Location {
+            line,
+            column,
+            text: text.to_owned(),
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {:?}",
+            self.line, self.column, self.text
+        )
+    }
+}
+
+/// A line of the puzzle input together with its 1-based line number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Location of `part`, which has to be a substring slice of this line.
+    pub fn locate(&self, part: &str) -> Location {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|offset| offset + part.len() <= self.text.len())
+            .expect("part is a slice of the line");
+        self.column(offset)
+    }
+
+    /// Location of the start of the line, for errors about the whole line.
+    pub fn start(&self) -> Location {
+        self.column(0)
+    }
+
+    /// Location just past the end of the line, for things that are missing.
+    pub fn end(&self) -> Location {
+        self.column(self.text.len())
+    }
+
+    fn column(&self, offset: usize) -> Location {
+        Location::new(
+            self.number,
+            self.text[..offset].chars().count() + 1,
+            self.text,
+        )
+    }
+}
+
+/// Iterates over the lines of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(idx, text)| Line {
+        number: idx + 1,
+        text,
+    })
+}
+
+/// Calls `f` with each line read from `reader`, numbered from 1, holding only
+/// a few lines in memory at a time. Like [`lines`] of the trimmed input, blank
+/// lines at the end are skipped and the last line has no trailing whitespace.
+pub fn for_each_line<E>(
+    reader: &mut dyn BufRead,
+    mut f: impl FnMut(Line) -> Result<(), E>,
+) -> color_eyre::Result<()>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut text = String::new();
+    let mut number = 0;
+    // the latest line that isn't blank, which is only passed on once we know
+    // whether it is the last one
+    let mut pending: Option<String> = None;
+    // blank lines after it, which are only passed on once we know that more
+    // follow
+    let mut blank = Vec::new();
+    loop {
+        text.clear();
+        if reader.read_line(&mut text)? == 0 {
+            if let Some(last) = pending {
+                f(Line {
+                    number: number - blank.len(),
+                    text: last.trim_end(),
+                })?;
+            }
+            return Ok(());
+        }
+        number += 1;
+        let text = text.strip_suffix('\n').unwrap_or(&text);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+
+        if text.trim().is_empty() {
+            blank.push(text.to_owned());
+            continue;
+        }
+        let first = number - blank.len();
+        if let Some(previous) = pending.replace(text.to_owned()) {
+            f(Line {
+                number: first - 1,
+                text: &previous,
+            })?;
+        }
+        for (idx, text) in blank.drain(..).enumerate() {
+            f(Line {
+                number: first + idx,
+                text: &text,
+            })?;
+        }
+    }
+}
+
+/// Location of the byte `offset` in `input`.
+pub fn locate(input: &str, offset: usize) -> Location {
+    let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = input[offset..]
+        .find('\n')
+        .map_or(input.len(), |idx| offset + idx);
+    let line = Line {
+        number: input[..offset].matches('\n').count() + 1,
+        text: input[line_start..line_end].trim_end_matches('\r'),
+    };
+    line.column((offset - line_start).min(line.text.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let input = "first\nsecond line\n\nlast";
+        let second = lines(input).nth(1).unwrap();
+        assert_eq!(second.number, 2);
+        assert_eq!(
+            second.locate(&second.text[7..]),
+            Location::new(2, 8, "second line")
+        );
+        assert_eq!(second.end().column, 12);
+
+        assert_eq!(locate(input, 13), second.locate(&second.text[7..]));
+        assert_eq!(locate(input, 0).line, 1);
+        assert_eq!(locate(input, 18).line, 3);
+        assert_eq!(locate(input, 18).text, "");
+        assert_eq!(locate(input, 19).line, 4);
+        assert_eq!(locate(input, 19).column, 1);
+        assert_eq!(locate(input, input.len()).line, 4);
+        assert_eq!(locate(input, input.len()).column, 5);
+
+        assert_eq!(
+            locate(input, 13).to_string(),
+            "line 2, column 8: \"second line\""
+        );
+    }
+    #[test]
+    fn test_for_each_line() {
+        let read = |input: &str| {
+            let mut read = Vec::new();
+            for_each_line(&mut input.as_bytes(), |line| {
+                read.push((line.number, line.text.to_owned()));
+                Ok::<_, std::fmt::Error>(())
+            })
+            .unwrap();
+            read
+        };
+        let expected = |input: &str| -> Vec<_> {
+            lines(input.trim_end())
+                .map(|line| (line.number, line.text.to_owned()))
+                .collect()
+        };
+
+        let input = "first\r\n\n  \nlast\n\n\n";
+        assert_eq!(read(input), expected(input));
+        assert_eq!(read(input).len(), 4);
+        // only the last line loses its trailing whitespace
+        let input = "first  \n1000  \r\n \n";
+        assert_eq!(read(input), expected(input));
+        assert_eq!(
+            read(input),
+            [(1, "first  ".to_owned()), (2, "1000".to_owned())]
+        );
+        assert_eq!(read(""), []);
+    }
+}
@@ -0,0 +1,22 @@
+use rand::{rngs::StdRng, Rng};
+
+/// A program of `size` instructions, but at least long enough to draw the
+/// whole screen, which keeps the sprite on the screen.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let (mut output, mut x, mut cycles) = (String::new(), 1, 0);
+    for idx in 0.. {
+        if idx >= size && cycles >= 240 {
+            break;
+        }
+        if rng.gen_bool(0.3) {
+            output += "noop\n";
+            cycles += 1;
+        } else {
+            let value = rng.gen_range(-x..=39 - x);
+            output += &format!("addx {}\n", value);
+            x += value;
+            cycles += 2;
+        }
+    }
+    output
+}
@@ -0,0 +1,37 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+const PRIMES: [u64; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+/// `size` monkeys, but at least two so that each can throw to another one.
+///
+/// The operations at most triple a worry level, so dividing it by three keeps
+/// it from overflowing, and the divisors are primes below 50, whose product
+/// still fits in 64 bits.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let count = size.max(2);
+    let mut monkeys = Vec::new();
+    for number in 0..count {
+        let items: Vec<_> = (0..rng.gen_range(0..=4))
+            .map(|_| rng.gen_range(1..100).to_string())
+            .collect();
+        let operation = match rng.gen_range(0..3) {
+            0 => format!("old + {}", rng.gen_range(1..=8)),
+            1 => format!("old * {}", rng.gen_range(2..=3)),
+            _ => "old + old".to_owned(),
+        };
+        let mut target = || (number + rng.gen_range(1..count)) % count;
+        let (if_true, if_false) = (target(), target());
+        monkeys.push(format!(
+            "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  \
+             Test: divisible by {}\n    If true: throw to monkey {}\n    \
+             If false: throw to monkey {}\n",
+            number,
+            items.join(", "),
+            operation,
+            PRIMES.choose(rng).unwrap(),
+            if_true,
+            if_false
+        ));
+    }
+    monkeys.join("\n")
+}
@@ -0,0 +1,31 @@
+use rand::{rngs::StdRng, Rng};
+
+/// A heightmap of `size` rows, but at least one, with random elevations apart
+/// from a climbable route from the start along its row to the last column
+/// and then up or down to the best signal. The rows are wide enough for the
+/// route to climb from `a` to `z` one elevation at a time.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let rows = size.max(1);
+    let columns = rng.gen_range(30..60);
+    let mut grid: Vec<Vec<u8>> = (0..rows)
+        .map(|_| (0..columns).map(|_| rng.gen_range(b'a'..=b'z')).collect())
+        .collect();
+
+    let (start, end) = (rng.gen_range(0..rows), rng.gen_range(0..rows));
+    let mut route: Vec<(usize, usize)> = (0..columns).map(|column| (start, column)).collect();
+    if end > start {
+        route.extend((start + 1..=end).map(|row| (row, columns - 1)));
+    } else {
+        route.extend((end..start).rev().map(|row| (row, columns - 1)));
+    }
+    let steps = route.len() - 1;
+    for (idx, &(row, column)) in route.iter().enumerate() {
+        grid[row][column] = b'a' + (idx * 25 / steps) as u8;
+    }
+    grid[start][0] = b'S';
+    grid[end][columns - 1] = b'E';
+
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
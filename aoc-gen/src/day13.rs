@@ -0,0 +1,23 @@
+use rand::{rngs::StdRng, Rng};
+
+// a random list nested at most `depth` levels deeper
+fn packet(rng: &mut StdRng, depth: usize) -> String {
+    let elements: Vec<_> = (0..rng.gen_range(0..=4))
+        .map(|_| {
+            if depth > 0 && rng.gen_bool(0.3) {
+                packet(rng, depth - 1)
+            } else {
+                rng.gen_range(0..=10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", elements.join(","))
+}
+
+/// `size` pairs of packets nested up to four levels deep.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let pairs: Vec<_> = (0..size)
+        .map(|_| format!("{}\n{}\n", packet(rng, 4), packet(rng, 4)))
+        .collect();
+    pairs.join("\n")
+}
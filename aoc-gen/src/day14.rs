@@ -0,0 +1,21 @@
+use rand::{rngs::StdRng, Rng};
+
+/// `size` paths of rock below and around the source, each a few segments that
+/// alternate between horizontal and vertical.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let (mut x, mut y) = (rng.gen_range(470..530u32), rng.gen_range(2..60u32));
+            let mut points = vec![format!("{},{}", x, y)];
+            for idx in 0..rng.gen_range(1..=4) {
+                if idx % 2 == 0 {
+                    x = (x + rng.gen_range(0..=10)).saturating_sub(5);
+                } else {
+                    y = (y + rng.gen_range(0..=6)).saturating_sub(3).max(1);
+                }
+                points.push(format!("{},{}", x, y));
+            }
+            points.join(" -> ") + "\n"
+        })
+        .collect()
+}
@@ -0,0 +1,14 @@
+use rand::{rngs::StdRng, Rng};
+
+/// A strategy guide of `size` rounds.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                ['A', 'B', 'C'][rng.gen_range(0..3)],
+                ['X', 'Y', 'Z'][rng.gen_range(0..3)]
+            )
+        })
+        .collect()
+}
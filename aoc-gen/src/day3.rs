@@ -0,0 +1,35 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Rucksacks of `size` elves, rounded up to whole groups of three. Every
+/// rucksack has exactly one item in both compartments, and every group exactly
+/// one badge.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut items = ITEMS.to_vec();
+        items.shuffle(rng);
+        let badge = items.pop().unwrap();
+
+        // each elf gets their own items, so that the badge is the only one the
+        // group shares, and splits them between the compartments, so that
+        // `common` is the only item in both
+        for own in items.chunks(items.len() / 3).take(3) {
+            let (common, own) = own.split_first().unwrap();
+            let (left, right) = own.split_at(own.len() / 2);
+            let len = rng.gen_range(2..=16);
+            let mut compartments = [vec![*common], vec![*common]];
+            compartments[rng.gen_range(0..2)].push(badge);
+            for (compartment, choices) in compartments.iter_mut().zip([left, right]) {
+                while compartment.len() < len {
+                    compartment.push(*choices.choose(rng).unwrap());
+                }
+                compartment.shuffle(rng);
+            }
+            output.extend(compartments.concat().into_iter().map(char::from));
+            output.push('\n');
+        }
+    }
+    output
+}
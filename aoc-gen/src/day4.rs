@@ -0,0 +1,12 @@
+use rand::{rngs::StdRng, Rng};
+
+/// `size` pairs of section assignments.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut range = || {
+        let start = rng.gen_range(1..=99);
+        format!("{}-{}", start, rng.gen_range(start..=99))
+    };
+    (0..size)
+        .map(|_| format!("{},{}\n", range(), range()))
+        .collect()
+}
@@ -0,0 +1,43 @@
+use rand::{rngs::StdRng, Rng};
+
+/// Nine stacks of crates and `size` moves, which never take the last crate
+/// off a stack, so that there are crates on top of every stack at the end.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut heights: Vec<usize> = (0..9).map(|_| rng.gen_range(1..=8)).collect();
+    heights[0] = heights[0].max(2);
+
+    let mut output = String::new();
+    for row in (0..*heights.iter().max().unwrap()).rev() {
+        let crates: Vec<_> = heights
+            .iter()
+            .map(|&height| {
+                if row < height {
+                    format!("[{}]", char::from(rng.gen_range(b'A'..=b'Z')))
+                } else {
+                    "   ".to_owned()
+                }
+            })
+            .collect();
+        output += &crates.join(" ");
+        output.push('\n');
+    }
+    let numbers: Vec<_> = (1..=heights.len()).map(|n| format!(" {} ", n)).collect();
+    output += &numbers.join(" ");
+    output += "\n\n";
+
+    for _ in 0..size {
+        // there's always a stack to take from, as no crates are ever removed
+        let from = loop {
+            let from = rng.gen_range(0..heights.len());
+            if heights[from] > 1 {
+                break from;
+            }
+        };
+        let to = (from + rng.gen_range(1..heights.len())) % heights.len();
+        let amount = rng.gen_range(1..heights[from]);
+        heights[from] -= amount;
+        heights[to] += amount;
+        output += &format!("move {} from {} to {}\n", amount, from + 1, to + 1);
+    }
+    output
+}
@@ -0,0 +1,16 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+/// A datastream of `size` characters, at least long enough for a
+/// start-of-message marker, which is put somewhere in it.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(14);
+    // with few different characters, markers rarely happen by chance
+    let mut stream: Vec<u8> = (0..size).map(|_| rng.gen_range(b'a'..=b'h')).collect();
+
+    let mut marker: Vec<u8> = (b'a'..=b'z').collect();
+    marker.shuffle(rng);
+    let start = rng.gen_range(0..=size - 14);
+    stream[start..start + 14].copy_from_slice(&marker[..14]);
+
+    String::from_utf8(stream).unwrap() + "\n"
+}
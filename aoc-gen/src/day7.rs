@@ -0,0 +1,85 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+#[derive(Debug, Default)]
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    files: Vec<(String, usize)>,
+}
+
+fn name(rng: &mut StdRng) -> String {
+    (0..rng.gen_range(1..=8))
+        .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+        .collect()
+}
+
+// `dirs[idx]` and everything below it, as explored by `$ cd` and `$ ls`
+fn transcript(dirs: &[Dir], idx: usize, output: &mut String) {
+    let dir = &dirs[idx];
+    *output += &format!("$ cd {}\n$ ls\n", dir.name);
+    for &sub in &dir.dirs {
+        *output += &format!("dir {}\n", dirs[sub].name);
+    }
+    for (name, size) in &dir.files {
+        *output += &format!("{} {}\n", size, name);
+    }
+    for &sub in &dir.dirs {
+        transcript(dirs, sub, output);
+        *output += "$ cd ..\n";
+    }
+}
+
+/// The transcript of exploring `size` directories. The disk is between 41 and
+/// 69 million full, so that something has to be deleted for the update.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut dirs = vec![Dir {
+        name: "/".to_owned(),
+        ..Default::default()
+    }];
+    for idx in 1..size.max(1) {
+        let parent = rng.gen_range(0..idx);
+        let name = loop {
+            let name = name(rng);
+            if dirs[parent].dirs.iter().all(|&sub| dirs[sub].name != name) {
+                break name;
+            }
+        };
+        dirs[parent].dirs.push(idx);
+        dirs.push(Dir {
+            name,
+            ..Default::default()
+        });
+    }
+
+    // a few large files and many small ones, as on a real disk
+    let mut weights = Vec::new();
+    for (idx, dir) in dirs.iter_mut().enumerate() {
+        let count = if idx == 0 { 1 } else { 0 } + rng.gen_range(0..=4);
+        for _ in 0..count {
+            let mut name = name(rng);
+            if rng.gen_bool(0.5) {
+                name = format!(
+                    "{}.{}",
+                    name,
+                    ["txt", "dat", "log", "bin"].choose(rng).unwrap()
+                );
+            }
+            if dir.files.iter().all(|file| file.0 != name) {
+                dir.files.push((name, 0));
+                weights.push(rng.gen_range(1..=1000u64).pow(3));
+            }
+        }
+    }
+    let total: u64 = weights.iter().sum();
+    let used = rng.gen_range(41_000_000..=69_000_000u64);
+    let mut weights = weights.into_iter();
+    for dir in &mut dirs {
+        for file in &mut dir.files {
+            file.1 = (weights.next().unwrap() * used / total).max(1) as usize;
+        }
+    }
+
+    let mut output = String::new();
+    transcript(&dirs, 0, &mut output);
+    output
+}
@@ -0,0 +1,15 @@
+use rand::{rngs::StdRng, Rng};
+
+/// A square grid of trees, `size` trees wide.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
@@ -0,0 +1,14 @@
+use rand::{rngs::StdRng, Rng};
+
+/// `size` motions of the head of the rope.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                ['L', 'R', 'U', 'D'][rng.gen_range(0..4)],
+                rng.gen_range(1..=20)
+            )
+        })
+        .collect()
+}
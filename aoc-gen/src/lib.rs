@@ -0,0 +1,125 @@
+//! Generators of random puzzle inputs, for testing the solvers on inputs of
+//! any size. Every generated input is valid, i.e. both parts can be solved.
+
+use rand::{rngs::StdRng, SeedableRng};
+
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+/// Makes an input of roughly `size` items (elves, rounds, moves, ...) for a day.
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+pub fn generator(day: u8) -> Option<Generator> {
+    match day {
+        1 => Some(day1::generate),
+        2 => Some(day2::generate),
+        3 => Some(day3::generate),
+        4 => Some(day4::generate),
+        5 => Some(day5::generate),
+        6 => Some(day6::generate),
+        7 => Some(day7::generate),
+        8 => Some(day8::generate),
+        9 => Some(day9::generate),
+        10 => Some(day10::generate),
+        11 => Some(day11::generate),
+        12 => Some(day12::generate),
+        13 => Some(day13::generate),
+        14 => Some(day14::generate),
+        _ => None,
+    }
+}
+
+/// Generates an input for `day`, the same one for the same `size` and `seed`.
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    generator(day).map(|generate| generate(&mut StdRng::seed_from_u64(seed), size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::{Solution, StreamingSolution};
+
+    fn check<S: Solution>() {
+        for size in [0, 1, 2, 3, 10, 200] {
+            for seed in 0..5 {
+                let input = generate(S::DAY, size, seed).unwrap();
+                if let Err(e) = aoc_core::solve::<S>(&input, None) {
+                    panic!(
+                        "day{} size {} seed {}: {:?}\n{}",
+                        S::DAY,
+                        size,
+                        seed,
+                        e,
+                        input
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solvable() {
+        check::<::day1::Day1>();
+        check::<::day2::Day2>();
+        check::<::day3::Day3>();
+        check::<::day4::Day4>();
+        check::<::day5::Day5>();
+        check::<::day6::Day6>();
+        check::<::day7::Day7>();
+        check::<::day8::Day8>();
+        check::<::day9::Day9>();
+        check::<::day10::Day10>();
+        check::<::day11::Day11>();
+        check::<::day12::Day12>();
+        check::<::day13::Day13>();
+        check::<::day14::Day14>();
+    }
+
+    fn check_stream<S: StreamingSolution>() {
+        for seed in 0..5 {
+            let input = generate(S::DAY, 200, seed).unwrap();
+            let report = aoc_core::solve::<S>(&input, None).unwrap();
+            let streamed = aoc_core::solve_reader::<S>(&mut input.as_bytes(), None).unwrap();
+            let values = |report: aoc_core::Report| -> Vec<_> {
+                report.answers.into_iter().map(|x| x.value).collect()
+            };
+            assert_eq!(
+                values(streamed),
+                values(report),
+                "day{} seed {}",
+                S::DAY,
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        check_stream::<::day1::Day1>();
+        check_stream::<::day2::Day2>();
+        check_stream::<::day3::Day3>();
+        check_stream::<::day4::Day4>();
+        check_stream::<::day6::Day6>();
+        check_stream::<::day10::Day10>();
+    }
+
+    #[test]
+    fn test_deterministic() {
+        for day in (1..=25).filter(|&day| generator(day).is_some()) {
+            assert_eq!(generate(day, 50, 7), generate(day, 50, 7));
+            assert_ne!(generate(day, 50, 7), generate(day, 50, 8));
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+use aoc_core::Solution;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn day_dir(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{}", day))
+}
+
+// the real input (if present) followed by every example in the day's fixtures,
+// but not the answers to them
+fn inputs(day: u8) -> Vec<(String, String)> {
+    let dir = day_dir(day);
+    let mut inputs = Vec::new();
+    if let Ok(input) = std::fs::read_to_string(dir.join("input")) {
+        inputs.push(("input".to_owned(), input));
+    }
+
+    let mut fixtures: Vec<_> = std::fs::read_dir(dir.join("fixtures"))
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    fixtures.sort();
+    for path in fixtures {
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        if name.starts_with("example") && path.extension().is_some_and(|ext| ext == "txt") {
+            inputs.push((name, std::fs::read_to_string(&path).unwrap()));
+        }
+    }
+    inputs
+}
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("day{}", S::DAY));
+    for (name, input) in inputs(S::DAY) {
+        group.bench_with_input(BenchmarkId::new("parse", &name), &input, |b, input| {
+            b.iter(|| S::parse(black_box(input)).unwrap())
+        });
+
+        let parsed = S::parse(&input).unwrap();
+        group.bench_with_input(BenchmarkId::new("part1", &name), &parsed, |b, parsed| {
+            b.iter(|| S::part1(black_box(parsed)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", &name), &parsed, |b, parsed| {
+            b.iter(|| S::part2(black_box(parsed)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c);
+    bench_day::<day2::Day2>(c);
+    bench_day::<day3::Day3>(c);
+    bench_day::<day4::Day4>(c);
+    bench_day::<day5::Day5>(c);
+    bench_day::<day6::Day6>(c);
+    bench_day::<day7::Day7>(c);
+    bench_day::<day8::Day8>(c);
+    bench_day::<day9::Day9>(c);
+    bench_day::<day10::Day10>(c);
+    bench_day::<day11::Day11>(c);
+    bench_day::<day12::Day12>(c);
+    bench_day::<day13::Day13>(c);
+    bench_day::<day14::Day14>(c);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,156 @@
+use std::{fmt::Display, path::Path};
+
+use aoc_core::{Day, Part};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+/// The answers to a day's real input that were accepted by the server.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`, which is empty if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Ledger::default());
+        }
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str) {
+        let entry = match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        };
+        *entry = Some(answer.to_owned());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Match,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// There is no verified answer to compare to.
+    Unverified(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Match => write!(f, "ok"),
+            Outcome::Mismatch { expected, actual } => {
+                write!(f, "MISMATCH, expected {:?} but got {:?}", expected, actual)
+            }
+            Outcome::Unverified(actual) => write!(f, "unverified, got {:?}", actual),
+        }
+    }
+}
+
+/// Solves both parts of `day` and compares the answers with the ledger.
+pub fn check(day: Day, input: &str, ledger: &Ledger) -> Result<Vec<(Part, Outcome)>> {
+    Ok(day
+        .solve(input, None)?
+        .answers
+        .into_iter()
+        .map(|answer| {
+            let actual = answer.value.to_string();
+            let outcome = match ledger.expected(answer.part) {
+                Some(expected) if expected == actual => Outcome::Match,
+                Some(expected) => Outcome::Mismatch {
+                    expected: expected.to_owned(),
+                    actual,
+                },
+                None => Outcome::Unverified(actual),
+            };
+            (answer.part, outcome)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_ledger_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.toml");
+
+        let mut ledger = Ledger::load(&path).unwrap();
+        assert_eq!(ledger, Ledger::default());
+
+        ledger.record(Part::Two, "#  #\n####\n#  #");
+        ledger.save(&path).unwrap();
+        ledger.record(Part::One, "42");
+        ledger.save(&path).unwrap();
+
+        let loaded = Ledger::load(&path).unwrap();
+        assert_eq!(loaded.expected(Part::One), Some("42"));
+        assert_eq!(loaded.expected(Part::Two), Some("#  #\n####\n#  #"));
+    }
+
+    #[test]
+    fn test_outcomes() {
+        let day = days::get(1).unwrap();
+        let input =
+            std::fs::read_to_string(days::workspace_dir().join("day1/fixtures/example.txt"))
+                .unwrap();
+        let ledger = Ledger {
+            part1: Some("1".to_owned()),
+            part2: None,
+        };
+
+        assert_eq!(
+            check(day, &input, &ledger).unwrap(),
+            vec![
+                (
+                    Part::One,
+                    Outcome::Mismatch {
+                        expected: "1".to_owned(),
+                        actual: "24000".to_owned()
+                    }
+                ),
+                (Part::Two, Outcome::Unverified("45000".to_owned())),
+            ]
+        );
+    }
+
+    // the real inputs must still produce the answers that were accepted
+    #[test]
+    fn test_verified_answers() {
+        for day in days::all() {
+            let input = days::input_path(day.number());
+            if !input.exists() {
+                continue;
+            }
+            let input = std::fs::read_to_string(input).unwrap();
+            let ledger = Ledger::load(&days::answers_path(day.number())).unwrap();
+            for (part, outcome) in check(day, &input, &ledger).unwrap() {
+                if let Outcome::Mismatch { .. } = outcome {
+                    panic!("day{} {}: {}", day.number(), part, outcome);
+                }
+            }
+        }
+    }
+}
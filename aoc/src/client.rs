@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use aoc_core::Part;
+use color_eyre::{eyre::eyre, Result};
+use structopt::StructOpt;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u16 = 2022;
+const USER_AGENT: &str = "github.com/dkales/adventofcode2022";
+
+#[derive(Debug, StructOpt)]
+pub struct ClientOpts {
+    /// Server to talk to, e.g. a local stub for testing
+    #[structopt(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Session cookie; defaults to the contents of ~/.config/aoc/session
+    #[structopt(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+}
+
+impl ClientOpts {
+    pub fn client(self) -> Result<Client> {
+        let session = match self.session {
+            Some(session) => session,
+            None => {
+                let path = session_path()?;
+                std::fs::read_to_string(&path)
+                    .map_err(|e| {
+                        eyre!(
+                            "no session token in AOC_SESSION or {}: {}",
+                            path.display(),
+                            e
+                        )
+                    })?
+                    .trim()
+                    .to_owned()
+            }
+        };
+        Ok(Client::new(self.base_url, session))
+    }
+}
+
+fn session_path() -> Result<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME").ok_or_else(|| eyre!("HOME is not set"))?)
+            .join(".config"),
+    };
+    Ok(config.join("aoc").join("session"))
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Client {
+            base_url: base_url.into(),
+            session: session.into(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    fn url(&self, day: u8, path: &str) -> String {
+        format!(
+            "{}/{}/day/{}{}",
+            self.base_url.trim_end_matches('/'),
+            YEAR,
+            day,
+            path
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn input(&self, day: u8) -> Result<String> {
+        let response = self
+            .agent
+            .get(&self.url(day, "/input"))
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| request_error(day, e))?;
+        Ok(response.into_string()?)
+    }
+
+    /// Posts an answer and returns the HTML page the server responds with.
+    pub fn answer(&self, day: u8, part: Part, answer: &str) -> Result<String> {
+        let level = part.number().to_string();
+        let response = self
+            .agent
+            .post(&self.url(day, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(|e| request_error(day, e))?;
+        Ok(response.into_string()?)
+    }
+}
+
+fn request_error(day: u8, e: ureq::Error) -> color_eyre::Report {
+    match e {
+        ureq::Error::Status(code, response) => eyre!(
+            "request for day {} failed with status {}: {}",
+            day,
+            code,
+            response.into_string().unwrap_or_default().trim()
+        ),
+        e => eyre!("request for day {} failed: {}", day, e),
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::{Path, PathBuf};
+
+use aoc_core::Day;
+
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::streaming::<day1::Day1>(),
+        Day::streaming::<day2::Day2>(),
+        Day::streaming::<day3::Day3>(),
+        Day::streaming::<day4::Day4>(),
+        Day::new::<day5::Day5>(),
+        Day::streaming::<day6::Day6>(),
+        Day::new::<day7::Day7>(),
+        Day::new::<day8::Day8>(),
+        Day::new::<day9::Day9>(),
+        Day::streaming::<day10::Day10>(),
+        Day::new::<day11::Day11>(),
+        Day::new::<day12::Day12>(),
+        Day::new::<day13::Day13>(),
+        Day::new::<day14::Day14>(),
+    ]
+}
+
+pub fn get(day: u8) -> Option<Day> {
+    all().into_iter().find(|d| d.number() == day)
+}
+
+pub fn workspace_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc crate lives inside the workspace")
+}
+
+pub fn input_path(day: u8) -> PathBuf {
+    workspace_dir().join(format!("day{}", day)).join("input")
+}
+
+pub fn answers_path(day: u8) -> PathBuf {
+    workspace_dir()
+        .join(format!("day{}", day))
+        .join("answers.toml")
+}
@@ -0,0 +1,197 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+
+/// The examples of a puzzle and the answers to them, as found on its page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Examples {
+    /// The contents of each distinct `<pre><code>` block, in order.
+    pub inputs: Vec<String>,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+// removes the markup within a block, e.g. the highlighting, and decodes entities
+fn text(html: &str) -> String {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    tags.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Extracts the examples from a saved puzzle page.
+///
+/// Each part is described in its own `<article>`, and the answer to its example
+/// is the last highlighted code in it, like `<code><em>24000</em></code>`.
+pub fn extract(html: &str) -> Examples {
+    let articles = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let blocks = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let answers =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+
+    let mut articles: Vec<&str> = articles
+        .captures_iter(html)
+        .map(|x| x.get(1).unwrap().as_str())
+        .collect();
+    if articles.is_empty() {
+        articles.push(html);
+    }
+
+    let mut examples = Examples::default();
+    for article in &articles {
+        for block in blocks.captures_iter(article) {
+            let input = text(&block[1]);
+            // the second part often repeats the example of the first
+            if !examples.inputs.contains(&input) {
+                examples.inputs.push(input);
+            }
+        }
+    }
+    let answer = |article: &str| {
+        let answer = answers.captures_iter(article).last()?;
+        Some(text(answer.get(1).or_else(|| answer.get(2))?.as_str()))
+    };
+    examples.part1 = articles.first().and_then(|article| answer(article));
+    examples.part2 = articles.get(1).and_then(|article| answer(article));
+    examples
+}
+
+/// The file the `idx`th example is written to, `example.txt` for the first.
+pub fn example_path(dir: &Path, idx: usize) -> PathBuf {
+    match idx {
+        0 => dir.join("example.txt"),
+        _ => dir.join(format!("example{}.txt", idx + 1)),
+    }
+}
+
+/// Writes the examples to `dir`, e.g. `dayN/fixtures`, returning the files
+/// written. Files with other contents are only overwritten if `force` is set,
+/// but empty ones, like those of a new day, always are.
+pub fn write(dir: &Path, examples: &Examples, force: bool) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<(PathBuf, String)> = examples
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(idx, input)| (example_path(dir, idx), input.clone()))
+        .collect();
+    for (name, answer) in [
+        ("answer1.txt", &examples.part1),
+        ("answer2.txt", &examples.part2),
+    ] {
+        if let Some(answer) = answer {
+            files.push((dir.join(name), format!("{}\n", answer)));
+        }
+    }
+
+    for (path, contents) in &files {
+        let existing = std::fs::read_to_string(path).unwrap_or_default();
+        if !force && !existing.is_empty() && existing != *contents {
+            return Err(eyre!(
+                "{} already exists, use --force to overwrite it",
+                path.display()
+            ));
+        }
+    }
+    std::fs::create_dir_all(dir)?;
+    for (path, contents) in &files {
+        std::fs::write(path, contents)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>For example, suppose the Elves finish writing their items' <em>Calories</em> like this:</p>
+<pre><code>1000
+2000
+
+4000
+</code></pre>
+<p>In the example above, this is <em><code>4000</code></em> (carried by the second Elf).</p>
+<p>Find the Elf carrying the most Calories. <em>How many total Calories is that Elf carrying?</em></p>
+</article>
+<p>Your puzzle answer was <code>69281</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>1000
+2000
+
+4000
+</code></pre>
+<pre><code>a &lt;<em>b</em>&gt; &amp;&amp; c</code></pre>
+<p>In the example above, the top elves are carrying <code>4000</code> and <code>3000</code>, <code><em>7000</em></code> in total.</p>
+</article>
+</main>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            extract(PAGE),
+            Examples {
+                inputs: vec!["1000\n2000\n\n4000\n".to_owned(), "a <b> && c".to_owned()],
+                part1: Some("4000".to_owned()),
+                part2: Some("7000".to_owned()),
+            }
+        );
+
+        // only the first part is shown before solving it
+        let (first, _) = PAGE
+            .split_once(r#"<article class="day-desc"><h2 id="part2">"#)
+            .unwrap();
+        let examples = extract(first);
+        assert_eq!(examples.inputs.len(), 1);
+        assert_eq!(examples.part1.as_deref(), Some("4000"));
+        assert_eq!(examples.part2, None);
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().join("fixtures");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("example.txt"), "").unwrap();
+
+        let examples = extract(PAGE);
+        let written = write(&dir, &examples, false).unwrap();
+        assert_eq!(
+            written,
+            vec![
+                dir.join("example.txt"),
+                dir.join("example2.txt"),
+                dir.join("answer1.txt"),
+                dir.join("answer2.txt")
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("example.txt")).unwrap(),
+            "1000\n2000\n\n4000\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("answer2.txt")).unwrap(),
+            "7000\n"
+        );
+
+        // writing the same again is fine, changing what is there is not
+        assert!(write(&dir, &examples, false).is_ok());
+        std::fs::write(dir.join("answer1.txt"), "24000\n").unwrap();
+        assert!(write(&dir, &examples, false).is_err());
+        assert!(write(&dir, &examples, true).is_ok());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("answer1.txt")).unwrap(),
+            "4000\n"
+        );
+    }
+}
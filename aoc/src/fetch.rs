@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use color_eyre::Result;
+
+use crate::client::Client;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    Cached,
+}
+
+/// Downloads the input of `day` to `path`, unless it is already there.
+pub fn fetch_input(client: &Client, day: u8, path: &Path) -> Result<Fetched> {
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = client.input(day)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, input)?;
+
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::StubServer;
+
+    #[test]
+    fn test_download_and_cache() {
+        let server = StubServer::start(200, "1000\n2000\n");
+        let client = Client::new(server.url(), "secret");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day1").join("input");
+
+        assert_eq!(fetch_input(&client, 1, &path).unwrap(), Fetched::Downloaded);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+
+        assert_eq!(fetch_input(&client, 1, &path).unwrap(), Fetched::Cached);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_error_status() {
+        let server = StubServer::start(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+        let client = Client::new(server.url(), "expired");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input");
+
+        let err = fetch_input(&client, 3, &path).unwrap_err();
+        assert!(err.to_string().contains("400"));
+        assert!(err.to_string().contains("Please log in"));
+        assert!(!path.exists());
+    }
+}
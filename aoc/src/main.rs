@@ -0,0 +1,378 @@
+use std::{
+    path::PathBuf,
+    thread,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_core::{alloc::CountingAllocator, Day, Format, Part, Report};
+use client::ClientOpts;
+use color_eyre::{eyre::eyre, Result};
+use structopt::StructOpt;
+
+mod check;
+mod client;
+mod days;
+mod examples;
+mod fetch;
+mod new;
+#[cfg(test)]
+mod stub;
+mod submit;
+mod timing;
+mod watch;
+
+// it only counts, and the reports only include, allocations made with
+// `run --profile-alloc`
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "aoc", about = "Advent of Code 2022")]
+struct Args {
+    /// Log the solvers' progress to stderr, more with -vv; RUST_LOG takes precedence
+    #[structopt(short, long, parse(from_occurrences), global = true)]
+    verbose: u8,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Runs the solver of a single day, or of every day with --all
+    Run {
+        #[structopt(required_unless = "all")]
+        day: Option<u8>,
+        /// Only solve the given part (1 or 2)
+        #[structopt(short, long)]
+        part: Option<Part>,
+        /// Input files to solve, `-` for stdin; defaults to dayN/input in the workspace
+        #[structopt(short, long, parse(from_os_str))]
+        input: Vec<PathBuf>,
+        #[structopt(long, conflicts_with_all = &["day", "input"])]
+        all: bool,
+        /// With --all, solve one day after the other instead of all at once
+        #[structopt(long, requires = "all")]
+        serial: bool,
+        /// Solve while reading the input, for inputs too large to fit in memory
+        #[structopt(long, conflicts_with = "all")]
+        stream: bool,
+        /// Report the allocations, bytes allocated and peak heap of each phase;
+        /// implies --serial with --all
+        #[structopt(long)]
+        profile_alloc: bool,
+        #[structopt(long, default_value = "text", possible_values = &["text", "json"])]
+        format: Format,
+    },
+    /// Downloads the puzzle input of a day to dayN/input, unless already there
+    Fetch {
+        day: u8,
+        #[structopt(flatten)]
+        client: ClientOpts,
+    },
+    /// Creates the crate dayN from a template and adds it to the workspace and the runner
+    New {
+        day: u8,
+        /// Title of the puzzle, for the crate's documentation
+        #[structopt(long)]
+        title: Option<String>,
+        /// Also download the input
+        #[structopt(long)]
+        fetch: bool,
+        #[structopt(flatten)]
+        client: ClientOpts,
+    },
+    /// Extracts the examples and their answers from a saved puzzle page into dayN/fixtures
+    Examples {
+        day: u8,
+        /// The puzzle's page, as saved by the browser
+        #[structopt(parse(from_os_str))]
+        page: PathBuf,
+        /// Overwrite fixtures with other contents
+        #[structopt(long)]
+        force: bool,
+    },
+    /// Submits the answer to a part, computing it from dayN/input if not given
+    Submit {
+        day: u8,
+        part: Part,
+        answer: Option<String>,
+        #[structopt(flatten)]
+        client: ClientOpts,
+    },
+    /// Reruns a day whenever its sources or inputs change, showing how the answers changed
+    Watch {
+        day: u8,
+        /// Input files to watch; defaults to dayN/input in the workspace
+        #[structopt(short, long, parse(from_os_str))]
+        input: Vec<PathBuf>,
+    },
+    /// Prints a random input for a day, e.g. to pipe into `aoc run N --input -`
+    Gen {
+        day: u8,
+        /// Number of items (elves, rounds, moves, directories, ...) to generate
+        #[structopt(long, default_value = "1000")]
+        size: usize,
+        /// Seed of the input; random by default
+        #[structopt(long)]
+        seed: Option<u64>,
+    },
+    /// Compares the answers for the real inputs with those in dayN/answers.toml
+    Check {
+        /// Defaults to all days
+        day: Option<u8>,
+    },
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    aoc_core::init_tracing(args.verbose);
+
+    match args.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            all,
+            serial,
+            stream,
+            profile_alloc,
+            format,
+        } => {
+            if profile_alloc {
+                aoc_core::alloc::enable();
+            }
+            if all {
+                // the allocations of days solved at once can't be told apart
+                return run_all(part, format, serial || profile_alloc);
+            }
+            let day = day.expect("structopt requires a day without --all");
+            let solver = days::get(day).ok_or_else(|| eyre!("no solver for day {}", day))?;
+            let inputs = if input.is_empty() {
+                vec![days::input_path(day)]
+            } else {
+                input
+            };
+            if stream {
+                aoc_core::run_readers(&inputs, format, |reader| solver.solve_reader(reader, part))
+            } else {
+                run(solver, &inputs, part, format)
+            }
+        }
+        Command::Fetch { day, client } => {
+            let path = days::input_path(day);
+            match fetch::fetch_input(&client.client()?, day, &path)? {
+                fetch::Fetched::Downloaded => println!("Downloaded {}", path.display()),
+                fetch::Fetched::Cached => println!("Already cached at {}", path.display()),
+            }
+            Ok(())
+        }
+        Command::New {
+            day,
+            title,
+            fetch,
+            client,
+        } => {
+            new::scaffold(days::workspace_dir(), day, title.as_deref())?;
+            println!("Created day{}", day);
+            if fetch {
+                let path = days::input_path(day);
+                fetch::fetch_input(&client.client()?, day, &path)?;
+                println!("Downloaded {}", path.display());
+            }
+            Ok(())
+        }
+        Command::Examples { day, page, force } => {
+            let html =
+                std::fs::read_to_string(&page).map_err(|e| eyre!("{}: {}", page.display(), e))?;
+            let examples = examples::extract(&html);
+            if examples.inputs.is_empty() {
+                return Err(eyre!("no examples in {}", page.display()));
+            }
+            let dir = days::workspace_dir()
+                .join(format!("day{}", day))
+                .join("fixtures");
+            for path in examples::write(&dir, &examples, force)? {
+                println!("Wrote {}", path.display());
+            }
+            for (part, answer) in [(1, &examples.part1), (2, &examples.part2)] {
+                if answer.is_none() {
+                    eprintln!("No answer to the example of part {} found", part);
+                }
+            }
+            Ok(())
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            client,
+        } => {
+            // the solver's answer to the real input, which is what the ledger
+            // holds and `check` compares against
+            let solve = || -> Result<String> {
+                let solver = days::get(day).ok_or_else(|| eyre!("no solver for day {}", day))?;
+                let input = std::fs::read_to_string(days::input_path(day))?;
+                Ok(solver.solve(&input, Some(part))?.answers[0]
+                    .value
+                    .to_string())
+            };
+            let (answer, solved) = match answer {
+                Some(answer) => (answer, None),
+                None => {
+                    let answer = solve()?;
+                    if answer.contains('\n') {
+                        // like the letters drawn by day 10, which have to be read off
+                        return Err(eyre!(
+                            "the answer spans several lines, submit what it reads as instead:\n{}",
+                            answer
+                        ));
+                    }
+                    println!("Submitting {}", answer);
+                    (answer.clone(), Some(answer))
+                }
+            };
+            let cooldown = submit::Cooldown::new(days::workspace_dir().join(".aoc-cooldown"));
+            let verdict = submit::submit(&client.client()?, &cooldown, day, part, &answer)?;
+            println!("{}", verdict);
+            if verdict == submit::Verdict::Correct {
+                let solved = match solved {
+                    Some(solved) => Ok(solved),
+                    None => solve(),
+                };
+                match solved {
+                    Ok(solved) if solved == answer => {
+                        let path = days::answers_path(day);
+                        let mut ledger = check::Ledger::load(&path)?;
+                        ledger.record(part, &solved);
+                        ledger.save(&path)?;
+                    }
+                    _ => eprintln!(
+                        "Not recording the answer to part {}: the solver doesn't give it",
+                        part
+                    ),
+                }
+            }
+            Ok(())
+        }
+        Command::Watch { day, input } => {
+            days::get(day).ok_or_else(|| eyre!("no solver for day {}", day))?;
+            watch::watch(day, input)
+        }
+        Command::Gen { day, size, seed } => {
+            let seed = match seed {
+                Some(seed) => seed,
+                None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64,
+            };
+            let input = aoc_gen::generate(day, size, seed)
+                .ok_or_else(|| eyre!("no generator for day {}", day))?;
+            eprintln!(
+                "Generated day {} input of size {} with seed {}",
+                day, size, seed
+            );
+            print!("{}", input);
+            Ok(())
+        }
+        Command::Check { day } => {
+            let days = match day {
+                Some(day) => {
+                    vec![days::get(day).ok_or_else(|| eyre!("no solver for day {}", day))?]
+                }
+                None => days::all(),
+            };
+            check(days)
+        }
+    }
+}
+
+fn run(day: Day, inputs: &[PathBuf], part: Option<Part>, format: Format) -> Result<()> {
+    aoc_core::run_inputs(inputs, format, |input| day.solve(input, part))
+}
+
+fn run_all(part: Option<Part>, format: Format, serial: bool) -> Result<()> {
+    let mut jobs = Vec::new();
+    for day in days::all() {
+        let input = days::input_path(day.number());
+        if !input.exists() {
+            eprintln!(
+                "Skipping day {}: no input at {}",
+                day.number(),
+                input.display()
+            );
+            continue;
+        }
+        jobs.push((
+            day,
+            input.display().to_string(),
+            aoc_core::read_input(&input)?,
+        ));
+    }
+
+    let solve = |(day, name, input): &(Day, String, String)| {
+        let mut report = day.solve(input, part)?;
+        report.input = Some(name.clone());
+        Ok(report)
+    };
+    let start = Instant::now();
+    let reports: Vec<Result<Report>> = if serial {
+        jobs.iter().map(solve).collect()
+    } else {
+        thread::scope(|scope| {
+            let threads: Vec<_> = jobs
+                .iter()
+                .map(|job| (job.0, scope.spawn(|| solve(job))))
+                .collect();
+            threads
+                .into_iter()
+                .map(|(day, thread)| {
+                    thread
+                        .join()
+                        .unwrap_or_else(|_| Err(eyre!("day {} panicked", day.number())))
+                })
+                .collect()
+        })
+    };
+    let wall = start.elapsed();
+    let reports = reports.into_iter().collect::<Result<Vec<_>>>()?;
+
+    for report in &reports {
+        if format == Format::Text {
+            println!("Day {}", report.day);
+        }
+        report.print(format);
+    }
+    if format == Format::Text {
+        println!();
+        print!("{}", timing::table(&reports, wall));
+    }
+
+    Ok(())
+}
+
+fn check(days: Vec<Day>) -> Result<()> {
+    let mut mismatches = 0;
+    for day in days {
+        let input = days::input_path(day.number());
+        if !input.exists() {
+            eprintln!(
+                "Skipping day {}: no input at {}",
+                day.number(),
+                input.display()
+            );
+            continue;
+        }
+        let input = std::fs::read_to_string(input)?;
+        let ledger = check::Ledger::load(&days::answers_path(day.number()))?;
+
+        for (part, outcome) in check::check(day, &input, &ledger)? {
+            if let check::Outcome::Mismatch { .. } = outcome {
+                mismatches += 1;
+            }
+            println!("day{} {}: {}", day.number(), part, outcome);
+        }
+    }
+
+    if mismatches > 0 {
+        return Err(eyre!("{} answers differ from the ledger", mismatches));
+    }
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::path::Path;
+
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    ("fixtures/example.txt", ""),
+    ("fixtures/answer1.txt", ""),
+    ("fixtures/answer2.txt", ""),
+];
+
+fn render(template: &str, day: u8, title: Option<&str>) -> String {
+    template.replace("{{day}}", &day.to_string()).replace(
+        "{{title}}",
+        &title.map(|t| format!(": {}", t)).unwrap_or_default(),
+    )
+}
+
+/// Adds `line` for `day` to a list of such lines in `text`, keeping the days in
+/// order. `pattern` matches the lines of the list, capturing their day.
+pub fn insert_day(text: &str, day: u8, line: &str, pattern: &str) -> Result<String> {
+    let pattern = Regex::new(pattern)?;
+    let lines: Vec<&str> = text.lines().collect();
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, pattern.captures(line)?[1].parse().ok()?)))
+        .collect();
+
+    if days.iter().any(|&(_, other)| other == day) {
+        return Ok(text.to_owned());
+    }
+    let at = match days.iter().find(|&&(_, other)| other > day) {
+        Some(&(idx, _)) => idx,
+        None => {
+            days.last()
+                .ok_or_else(|| eyre!("no line like {}", pattern))?
+                .0
+                + 1
+        }
+    };
+
+    let mut lines: Vec<String> = lines.into_iter().map(str::to_owned).collect();
+    lines.insert(at, line.to_owned());
+    Ok(lines.join("\n") + "\n")
+}
+
+fn register(path: &Path, day: u8, line: &str, pattern: &str) -> Result<()> {
+    let text = std::fs::read_to_string(path)?;
+    std::fs::write(path, insert_day(&text, day, line, pattern)?)?;
+    Ok(())
+}
+
+/// Creates the crate `dayN` in `workspace` and registers it as a workspace
+/// member and as a day of the `aoc` runner.
+pub fn scaffold(workspace: &Path, day: u8, title: Option<&str>) -> Result<()> {
+    let dir = workspace.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(eyre!("{} already exists", dir.display()));
+    }
+
+    for (path, template) in TEMPLATES {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, render(template, day, title))?;
+    }
+
+    register(
+        &workspace.join("Cargo.toml"),
+        day,
+        &format!("    \"day{}\",", day),
+        r#"^\s*"day(\d+)",$"#,
+    )?;
+    register(
+        &workspace.join("aoc").join("Cargo.toml"),
+        day,
+        &format!("day{0} = {{ path = \"../day{0}\" }}", day),
+        r"^day(\d+) = ",
+    )?;
+    register(
+        &workspace.join("aoc").join("src").join("days.rs"),
+        day,
+        &format!("        Day::new::<day{0}::Day{0}>(),", day),
+        r"Day::\w+::<day(\d+)::",
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_insert_day() {
+        let members = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day10\",\n]\n";
+        let pattern = r#"^\s*"day(\d+)",$"#;
+
+        assert_eq!(
+            insert_day(members, 9, "    \"day9\",", pattern).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day9\",\n    \"day10\",\n]\n"
+        );
+        assert_eq!(
+            insert_day(members, 11, "    \"day11\",", pattern).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day10\",\n    \"day11\",\n]\n"
+        );
+        assert_eq!(
+            insert_day(members, 10, "    \"day10\",", pattern).unwrap(),
+            members
+        );
+        assert!(insert_day("[workspace]\n", 1, "    \"day1\",", pattern).is_err());
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let workspace = tempfile::tempdir().unwrap();
+        let workspace = workspace.path();
+        std::fs::create_dir_all(workspace.join("aoc/src")).unwrap();
+        for path in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/days.rs"] {
+            std::fs::copy(days::workspace_dir().join(path), workspace.join(path)).unwrap();
+        }
+
+        scaffold(workspace, 9, Some("Rope Bridge")).unwrap();
+
+        let lib = std::fs::read_to_string(workspace.join("day9/src/lib.rs")).unwrap();
+        assert!(lib.starts_with("//! Advent of Code 2022 Day 9: Rope Bridge\n"));
+        assert!(lib.contains("impl Solution for Day9 {\n    const DAY: u8 = 9;"));
+        let main = std::fs::read_to_string(workspace.join("day9/src/main.rs")).unwrap();
+        assert!(main.contains("aoc_core::run::<day9::Day9>"));
+        assert!(workspace.join("day9/fixtures/example.txt").exists());
+        assert!(workspace.join("day9/fixtures/answer1.txt").exists());
+
+        let members = std::fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+        assert!(members.contains("    \"day8\",\n    \"day9\",\n    \"day10\",\n"));
+        let deps = std::fs::read_to_string(workspace.join("aoc/Cargo.toml")).unwrap();
+        assert!(deps.contains("day9 = { path = \"../day9\" }\nday10 = "));
+        let days = std::fs::read_to_string(workspace.join("aoc/src/days.rs")).unwrap();
+        assert!(
+            days.contains("Day::new::<day9::Day9>(),\n        Day::streaming::<day10::Day10>()")
+        );
+
+        assert!(scaffold(workspace, 9, None).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_core::Part;
+use color_eyre::Result;
+use regex::Regex;
+
+use crate::client::Client;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh {
+        wait: Duration,
+    },
+    TooLow {
+        wait: Duration,
+    },
+    Incorrect {
+        wait: Duration,
+    },
+    Wait(Duration),
+    AlreadySolved,
+    /// A response we don't recognize, with the text of its article.
+    Unknown(String),
+}
+
+impl Verdict {
+    pub fn parse(page: &str) -> Self {
+        let text = article_text(page);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            let wait = penalty(&text);
+            if text.contains("too high") {
+                Verdict::TooHigh { wait }
+            } else if text.contains("too low") {
+                Verdict::TooLow { wait }
+            } else {
+                Verdict::Incorrect { wait }
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::Wait(time_left(&text).unwrap_or(Duration::from_secs(60)))
+        } else if text.contains("solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(text)
+        }
+    }
+
+    /// How long we have to wait before the next submission.
+    pub fn cooldown(&self) -> Option<Duration> {
+        match self {
+            Verdict::TooHigh { wait } | Verdict::TooLow { wait } | Verdict::Incorrect { wait } => {
+                Some(*wait)
+            }
+            Verdict::Wait(wait) => Some(*wait),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh { .. } => write!(f, "Wrong answer: too high"),
+            Verdict::TooLow { .. } => write!(f, "Wrong answer: too low"),
+            Verdict::Incorrect { .. } => write!(f, "Wrong answer"),
+            Verdict::Wait(wait) => write!(f, "Answered too recently, wait {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "This part is already solved"),
+            Verdict::Unknown(text) => write!(f, "Unexpected response: {}", text),
+        }
+    }
+}
+
+// the interesting part of the page is in the <article>, with all tags stripped
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    tags.replace_all(article, "").trim().to_owned()
+}
+
+// "You have 1m 5s left to wait."
+fn time_left(text: &str) -> Option<Duration> {
+    let regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    let cap = regex.captures(text)?;
+    let minutes: u64 = cap.get(1).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+    let seconds: u64 = cap[2].parse().ok()?;
+    Some(Duration::from_secs(minutes * 60 + seconds))
+}
+
+// "Please wait one minute before trying again." or "... wait 5 minutes ..."
+fn penalty(text: &str) -> Duration {
+    let regex = Regex::new(r"wait (\w+) minutes? before trying again").unwrap();
+    let minutes = regex
+        .captures(text)
+        .and_then(|cap| match &cap[1] {
+            "one" => Some(1),
+            n => n.parse().ok(),
+        })
+        .unwrap_or(1);
+    Duration::from_secs(minutes * 60)
+}
+
+/// Remembers until when the server will refuse new answers, so that we don't
+/// even try to submit before then.
+pub struct Cooldown {
+    path: PathBuf,
+}
+
+impl Cooldown {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Cooldown { path: path.into() }
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        let until: u64 = std::fs::read_to_string(&self.path)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        (UNIX_EPOCH + Duration::from_secs(until))
+            .duration_since(SystemTime::now())
+            .ok()
+    }
+
+    pub fn start(&self, wait: Duration) -> Result<()> {
+        let until = SystemTime::now().duration_since(UNIX_EPOCH)? + wait;
+        // round up, waiting a second too long is better than being rejected
+        std::fs::write(&self.path, (until.as_secs() + 1).to_string())?;
+        Ok(())
+    }
+}
+
+/// Submits `answer`, unless we know that we still have to wait.
+pub fn submit(
+    client: &Client,
+    cooldown: &Cooldown,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict> {
+    if let Some(wait) = cooldown.remaining() {
+        return Ok(Verdict::Wait(wait));
+    }
+
+    let verdict = Verdict::parse(&client.answer(day, part, answer)?);
+    if let Some(wait) = verdict.cooldown() {
+        cooldown.start(wait)?;
+    }
+
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::StubServer;
+
+    fn page(text: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let kats = vec![
+            (
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. [<a href=\"/2022/day/1\">Return to Day 1</a>]",
+                Verdict::TooHigh { wait: Duration::from_secs(60) },
+            ),
+            (
+                "That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.",
+                Verdict::TooLow { wait: Duration::from_secs(300) },
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data. (You guessed <span style=\"white-space:nowrap;\"><code>ABC</code>.)</span> Please wait one minute before trying again.",
+                Verdict::Incorrect { wait: Duration::from_secs(60) },
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait. [<a href=\"/2022/day/1\">Return to Day 1</a>]",
+                Verdict::Wait(Duration::from_secs(34)),
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 2m 10s left to wait.",
+                Verdict::Wait(Duration::from_secs(130)),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it? [<a href=\"/2022/day/1\">Return to Day 1</a>]",
+                Verdict::AlreadySolved,
+            ),
+            ("Something <em>new</em>", Verdict::Unknown("Something new".to_owned())),
+        ];
+
+        for (text, verdict) in kats {
+            assert_eq!(Verdict::parse(&page(text)), verdict);
+        }
+    }
+
+    #[test]
+    fn test_submit_and_cooldown() {
+        let server = StubServer::start(
+            200,
+            &page("That's not the right answer; your answer is too low.  Please wait one minute before trying again."),
+        );
+        let client = Client::new(server.url(), "secret");
+        let dir = tempfile::tempdir().unwrap();
+        let cooldown = Cooldown::new(dir.path().join("cooldown"));
+
+        let verdict = submit(&client, &cooldown, 4, Part::Two, "956").unwrap();
+        assert_eq!(
+            verdict,
+            Verdict::TooLow {
+                wait: Duration::from_secs(60)
+            }
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/4/answer");
+        assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+        assert_eq!(requests[0].body, "level=2&answer=956");
+
+        // the second attempt is refused locally, without asking the server
+        match submit(&client, &cooldown, 4, Part::Two, "957").unwrap() {
+            Verdict::Wait(wait) => assert!(wait > Duration::from_secs(50)),
+            verdict => panic!("unexpected verdict {:?}", verdict),
+        }
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_correct_has_no_cooldown() {
+        let server = StubServer::start(200, &page("That's the right answer!"));
+        let client = Client::new(server.url(), "secret");
+        let dir = tempfile::tempdir().unwrap();
+        let cooldown = Cooldown::new(dir.path().join("cooldown"));
+
+        assert_eq!(
+            submit(&client, &cooldown, 1, Part::One, "69281").unwrap(),
+            Verdict::Correct
+        );
+        assert_eq!(cooldown.remaining(), None);
+    }
+}
@@ -0,0 +1,123 @@
+use std::{fmt::Write, time::Duration};
+
+use aoc_core::{Part, Report};
+
+fn millis(time: Option<Duration>) -> String {
+    match time {
+        Some(time) => format!("{:.3} ms", time.as_secs_f64() * 1000.0),
+        None => "-".to_owned(),
+    }
+}
+
+fn part_time(report: &Report, part: Part) -> Option<Duration> {
+    report
+        .answers
+        .iter()
+        .find(|answer| answer.part == part)
+        .map(|answer| answer.time)
+}
+
+fn total(report: &Report) -> Duration {
+    report.parse_time
+        + report
+            .answers
+            .iter()
+            .map(|answer| answer.time)
+            .sum::<Duration>()
+}
+
+/// A table of the time each day spent in each phase, with the totals of every
+/// column, marking the slowest day. `wall` is how long the whole run took.
+pub fn table(reports: &[Report], wall: Duration) -> String {
+    let slowest = reports.iter().map(total).max();
+    let mut rows = vec![[
+        "Day".to_owned(),
+        "parse".to_owned(),
+        "part1".to_owned(),
+        "part2".to_owned(),
+        "total".to_owned(),
+    ]];
+    for report in reports {
+        rows.push([
+            report.day.to_string(),
+            millis(Some(report.parse_time)),
+            millis(part_time(report, Part::One)),
+            millis(part_time(report, Part::Two)),
+            millis(Some(total(report))),
+        ]);
+    }
+    let sum = |time: fn(&Report) -> Option<Duration>| {
+        let times: Vec<_> = reports.iter().filter_map(time).collect();
+        (!times.is_empty()).then(|| times.into_iter().sum())
+    };
+    rows.push([
+        "Total".to_owned(),
+        millis(sum(|r| Some(r.parse_time))),
+        millis(sum(|r| part_time(r, Part::One))),
+        millis(sum(|r| part_time(r, Part::Two))),
+        millis(sum(|r| Some(total(r)))),
+    ]);
+
+    let widths: Vec<usize> = (0..5)
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap())
+        .collect();
+    let mut output = String::new();
+    for (idx, row) in rows.iter().enumerate() {
+        write!(output, "{:<width$}", row[0], width = widths[0]).unwrap();
+        for (cell, width) in row.iter().zip(&widths).skip(1) {
+            write!(output, "  {:>width$}", cell, width = width).unwrap();
+        }
+        let report = idx.checked_sub(1).and_then(|idx| reports.get(idx));
+        if report.is_some_and(|report| reports.len() > 1 && Some(total(report)) == slowest) {
+            output += "  <- slowest";
+        }
+        output += "\n";
+    }
+    writeln!(output, "Wall clock: {}", millis(Some(wall))).unwrap();
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Answer;
+
+    fn report(day: u8, parse: u64, part1: u64, part2: Option<u64>) -> Report {
+        let answer = |part, time| Answer {
+            part,
+            value: 0usize.into(),
+            time: Duration::from_micros(time),
+            alloc: None,
+        };
+        let mut answers = vec![answer(Part::One, part1)];
+        answers.extend(part2.map(|time| answer(Part::Two, time)));
+        Report {
+            day,
+            input: None,
+            parse_time: Duration::from_micros(parse),
+            parse_alloc: None,
+            answers,
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let reports = vec![
+            report(1, 100, 20, Some(30)),
+            report(5, 1900000, 150, Some(250)),
+            report(10, 10, 2, None),
+        ];
+
+        assert_eq!(
+            table(&reports, Duration::from_millis(1901)),
+            "\
+Day          parse     part1     part2        total
+1         0.100 ms  0.020 ms  0.030 ms     0.150 ms
+5      1900.000 ms  0.150 ms  0.250 ms  1900.400 ms  <- slowest
+10        0.010 ms  0.002 ms         -     0.012 ms
+Total  1900.110 ms  0.172 ms  0.280 ms  1900.562 ms
+Wall clock: 1901.000 ms
+"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    time::Duration,
+};
+
+use color_eyre::{eyre::eyre, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
+
+use crate::days;
+
+/// How long to wait for further changes before rerunning, so that an editor
+/// saving several files only triggers a single run.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// One line of the output of `aoc run --format json`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RunAnswer {
+    pub input: Option<String>,
+    pub part: u8,
+    pub answer: serde_json::Value,
+}
+
+impl RunAnswer {
+    fn key(&self) -> (Option<String>, u8) {
+        (self.input.clone(), self.part)
+    }
+
+    fn text(&self) -> String {
+        match &self.answer {
+            serde_json::Value::String(x) => x.clone(),
+            x => x.to_string(),
+        }
+    }
+}
+
+pub fn parse_answers(output: &str) -> Result<Vec<RunAnswer>> {
+    output
+        .lines()
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// Nothing to compare to, e.g. on the first run.
+    New,
+    Unchanged,
+    Changed {
+        previous: String,
+    },
+}
+
+/// An answer of the latest run, compared to the one before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+    pub input: Option<String>,
+    pub part: u8,
+    pub answer: String,
+    pub change: Change,
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(input) = &self.input {
+            write!(f, "{} ", input)?;
+        }
+        if self.answer.contains('\n') {
+            write!(f, "part{}: \n{}", self.part, self.answer)?;
+        } else {
+            write!(f, "part{}: {}", self.part, self.answer)?;
+        }
+        match &self.change {
+            Change::New => Ok(()),
+            Change::Unchanged => write!(f, " (unchanged)"),
+            Change::Changed { previous }
+                if previous.contains('\n') || self.answer.contains('\n') =>
+            {
+                write!(f, " (changed)")
+            }
+            Change::Changed { previous } => write!(f, " (was {})", previous),
+        }
+    }
+}
+
+/// Compares the answers of a run with those of the previous one.
+pub fn diff(previous: &[RunAnswer], current: &[RunAnswer]) -> Vec<Diff> {
+    let previous: HashMap<_, _> = previous.iter().map(|x| (x.key(), x.text())).collect();
+    current
+        .iter()
+        .map(|answer| {
+            let text = answer.text();
+            let change = match previous.get(&answer.key()) {
+                None => Change::New,
+                Some(previous) if *previous == text => Change::Unchanged,
+                Some(previous) => Change::Changed {
+                    previous: previous.clone(),
+                },
+            };
+            Diff {
+                input: answer.input.clone(),
+                part: answer.part,
+                answer: text,
+                change,
+            }
+        })
+        .collect()
+}
+
+// rebuilds the runner, so that changes to the solution are picked up
+fn run_once(day: u8, inputs: &[PathBuf]) -> Result<Vec<RunAnswer>> {
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command
+        .current_dir(days::workspace_dir())
+        .args(["run", "--quiet", "--release", "-p", "aoc", "--", "run"])
+        .arg(day.to_string())
+        .args(["--format", "json"]);
+    for input in inputs {
+        command.arg("--input").arg(input);
+    }
+
+    let output = command.stderr(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err(eyre!("run failed with {}", output.status));
+    }
+    parse_answers(&String::from_utf8(output.stdout)?)
+}
+
+/// The files and directories whose changes trigger a rerun.
+struct Watched {
+    sources: PathBuf,
+    inputs: Vec<PathBuf>,
+}
+
+impl Watched {
+    fn is_relevant(&self, path: &Path) -> bool {
+        path.starts_with(&self.sources) || self.inputs.iter().any(|input| input == path)
+    }
+
+    fn watch(&self, watcher: &mut impl Watcher) -> Result<()> {
+        watcher.watch(&self.sources, RecursiveMode::Recursive)?;
+        // watching the directories instead of the files themselves also notices
+        // files that are created later, or replaced by editors
+        for input in &self.inputs {
+            let dir = input.parent().unwrap_or(Path::new("/"));
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        Ok(())
+    }
+}
+
+// blocks until one of the watched files changes, and the changes have settled
+fn wait_for_change(
+    events: &Receiver<notify::Result<notify::Event>>,
+    watched: &Watched,
+) -> Result<()> {
+    let is_relevant = |event: notify::Result<notify::Event>| -> Result<bool> {
+        Ok(event?.paths.iter().any(|path| watched.is_relevant(path)))
+    };
+
+    while !is_relevant(events.recv()?)? {}
+    loop {
+        match events.recv_timeout(DEBOUNCE) {
+            Ok(event) => {
+                is_relevant(event)?;
+            }
+            Err(RecvTimeoutError::Timeout) => return Ok(()),
+            Err(RecvTimeoutError::Disconnected) => return Err(eyre!("file watcher stopped")),
+        }
+    }
+}
+
+/// Reruns `day` on `inputs` (its `dayN/input` if empty) whenever the inputs or
+/// the day's sources change, printing how the answers changed.
+pub fn watch(day: u8, inputs: Vec<PathBuf>) -> Result<()> {
+    if inputs.iter().any(|input| input == Path::new("-")) {
+        return Err(eyre!("stdin can't be watched"));
+    }
+    let cwd = std::env::current_dir()?;
+    let inputs = if inputs.is_empty() {
+        vec![days::input_path(day)]
+    } else {
+        inputs.into_iter().map(|input| cwd.join(input)).collect()
+    };
+    let watched = Watched {
+        sources: days::workspace_dir()
+            .join(format!("day{}", day))
+            .join("src"),
+        inputs: inputs.clone(),
+    };
+
+    let (tx, events) = channel();
+    let mut watcher = RecommendedWatcher::new(tx, notify::Config::default())?;
+    watched.watch(&mut watcher)?;
+
+    let mut previous = Vec::new();
+    loop {
+        println!("==> Running day {} <==", day);
+        match run_once(day, &inputs) {
+            Ok(answers) => {
+                for diff in diff(&previous, &answers) {
+                    println!("{}", diff);
+                }
+                previous = answers;
+            }
+            Err(e) => eprintln!("Error: {}", e),
+        }
+        println!("Waiting for changes...");
+        wait_for_change(&events, &watched)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let first = parse_answers(
+            r#"{"day":1,"part":1,"answer":24000,"parse_time_ms":0.1,"solve_time_ms":0.1}
+{"day":1,"part":2,"answer":45000,"parse_time_ms":0.1,"solve_time_ms":0.1}
+"#,
+        )
+        .unwrap();
+        let second = parse_answers(
+            r#"{"day":1,"part":1,"answer":24000,"parse_time_ms":0.1,"solve_time_ms":0.1}
+{"day":1,"part":2,"answer":41000,"parse_time_ms":0.1,"solve_time_ms":0.1}
+"#,
+        )
+        .unwrap();
+
+        let diffs = diff(&[], &first);
+        assert!(diffs.iter().all(|diff| diff.change == Change::New));
+        assert_eq!(diffs[0].to_string(), "part1: 24000");
+
+        let diffs = diff(&first, &second);
+        assert_eq!(diffs[0].to_string(), "part1: 24000 (unchanged)");
+        assert_eq!(diffs[1].to_string(), "part2: 41000 (was 45000)");
+    }
+
+    #[test]
+    fn test_diff_per_input() {
+        let first = parse_answers(
+            r###"{"day":10,"input":"a","part":2,"answer":"##\n #","parse_time_ms":0.1,"solve_time_ms":0.1}
+{"day":10,"input":"b","part":2,"answer":"##\n #","parse_time_ms":0.1,"solve_time_ms":0.1}
+"###,
+        )
+        .unwrap();
+        let second = parse_answers(
+            r###"{"day":10,"input":"a","part":2,"answer":"##\n #","parse_time_ms":0.1,"solve_time_ms":0.1}
+{"day":10,"input":"b","part":2,"answer":"# \n #","parse_time_ms":0.1,"solve_time_ms":0.1}
+"###,
+        )
+        .unwrap();
+
+        let diffs = diff(&first, &second);
+        assert_eq!(diffs[0].to_string(), "a part2: \n##\n # (unchanged)");
+        assert_eq!(diffs[1].to_string(), "b part2: \n# \n # (changed)");
+    }
+
+    #[test]
+    fn test_relevant_paths() {
+        let watched = Watched {
+            sources: PathBuf::from("/aoc/day7/src"),
+            inputs: vec![PathBuf::from("/aoc/day7/input")],
+        };
+
+        assert!(watched.is_relevant(Path::new("/aoc/day7/src/lib.rs")));
+        assert!(watched.is_relevant(Path::new("/aoc/day7/input")));
+        assert!(!watched.is_relevant(Path::new("/aoc/day7/answers.toml")));
+        assert!(!watched.is_relevant(Path::new("/aoc/day8/src/lib.rs")));
+    }
+}
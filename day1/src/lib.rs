@@ -0,0 +1,189 @@
+//! Advent of Code 2022 Day 1: Calorie Counting
+
+use std::io::BufRead;
+
+use aoc_core::{Line, Location, Solution, StreamingSolution};
+use color_eyre::{eyre::eyre, Result};
+use itertools::Itertools;
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid calories at {0}")]
+    InvalidCalories(Location),
+    #[error("total calories of an elf, or of the top three, overflow at {0}")]
+    TooManyCalories(Location),
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+impl StreamingSolution for Day1 {
+    fn solve_reader(reader: &mut dyn BufRead) -> Result<(usize, usize)> {
+        let mut top = TopThree::default();
+        let mut total = 0;
+        aoc_core::for_each_line(reader, |line| {
+            if line.text.is_empty() {
+                top.add(total);
+                total = 0;
+                return Ok(());
+            }
+            total = add_calories(total, &top, &line)?;
+            Ok::<_, ParseError>(())
+        })?;
+        top.add(total);
+
+        let sum = top
+            .sum()
+            .ok_or_else(|| eyre!("the top three elves carry more calories than fit"))?;
+        Ok((top.0[0], sum))
+    }
+}
+
+/// The three largest totals so far, largest first.
+#[derive(Debug, Clone, Copy, Default)]
+struct TopThree([usize; 3]);
+
+impl TopThree {
+    fn add(&mut self, total: usize) {
+        if let Some(idx) = self.0.iter().position(|&other| total > other) {
+            self.0[idx..].rotate_right(1);
+            self.0[idx] = total;
+        }
+    }
+
+    fn sum(&self) -> Option<usize> {
+        self.0
+            .iter()
+            .try_fold(0usize, |sum, &total| sum.checked_add(total))
+    }
+}
+
+// adds the calories on `line` to the elf's `total`, which together with the
+// `top` three elves so far must not overflow either
+fn add_calories(total: usize, top: &TopThree, line: &Line) -> Result<usize, ParseError> {
+    let calories = line
+        .text
+        .parse::<usize>()
+        .map_err(|_| ParseError::InvalidCalories(line.locate(line.text)))?;
+    total
+        .checked_add(calories)
+        .filter(|&total| {
+            let mut top = *top;
+            top.add(total);
+            top.sum().is_some()
+        })
+        .ok_or_else(|| ParseError::TooManyCalories(line.locate(line.text)))
+}
+
+/// Parses the calorie list into the total calories carried by each elf.
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let mut elves = vec![0];
+    let mut top = TopThree::default();
+    for line in aoc_core::lines(input.trim_end()) {
+        // a blank line starts the next elf
+        if line.text.is_empty() {
+            top.add(*elves.last().unwrap());
+            elves.push(0);
+            continue;
+        }
+        let total = elves.last_mut().unwrap();
+        *total = add_calories(*total, &top, &line)?;
+    }
+    Ok(elves)
+}
+
+/// Calories carried by the elf carrying the most.
+pub fn solve_part1(elves: &[usize]) -> Result<usize> {
+    elves
+        .iter()
+        .copied()
+        .max()
+        .ok_or_else(|| eyre!("no elves in input"))
+}
+
+/// Calories carried by the top three elves combined.
+pub fn solve_part2(elves: &[usize]) -> Result<usize> {
+    let top: Vec<_> = elves.iter().sorted().rev().take(3).collect();
+    debug!(?top, "top three elves");
+    top.into_iter()
+        .try_fold(0usize, |sum, &total| sum.checked_add(total))
+        .ok_or_else(|| eyre!("the top three elves carry more calories than fit"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let elves = parse(kat).unwrap();
+        assert_eq!(solve_part1(&elves).unwrap(), 24000);
+        assert_eq!(solve_part2(&elves).unwrap(), 45000);
+    }
+    #[test]
+    fn test_stream() {
+        let kat = include_str!("../fixtures/example.txt");
+        assert_eq!(
+            Day1::solve_reader(&mut kat.as_bytes()).unwrap(),
+            (24000, 45000)
+        );
+        assert_eq!(
+            Day1::solve_reader(&mut "5\n\n1\n\n".as_bytes()).unwrap(),
+            (5, 6)
+        );
+        // trailing whitespace on the last line is trimmed like `parse` does
+        assert_eq!(
+            Day1::solve_reader(&mut "1000  \n".as_bytes()).unwrap(),
+            (1000, 1000)
+        );
+
+        let error = Day1::solve_reader(&mut "1000\n\n2000\n3k00\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ParseError>(),
+            parse("1000\n\n2000\n3k00\n").err().as_ref()
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("1000\n\n2000\n3k00\n").unwrap_err(),
+            ParseError::InvalidCalories(Location::new(4, 1, "3k00"))
+        );
+        assert_eq!(
+            parse("18446744073709551615\n1").unwrap_err(),
+            ParseError::TooManyCalories(Location::new(2, 1, "1"))
+        );
+        // the top three together overflow
+        let input = "9223372036854775807\n\n9223372036854775807\n\n3";
+        assert_eq!(
+            parse(input).unwrap_err(),
+            ParseError::TooManyCalories(Location::new(5, 1, "3"))
+        );
+        let error = Day1::solve_reader(&mut input.as_bytes()).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ParseError>(),
+            parse(input).err().as_ref()
+        );
+        assert!(solve_part2(&[usize::MAX, 1]).is_err());
+    }
+}
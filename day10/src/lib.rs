@@ -0,0 +1,214 @@
+//! Advent of Code 2022 Day 10: Cathode-Ray Tube
+
+use std::io::BufRead;
+
+use aoc_core::{Line, Location, Solution, StreamingSolution};
+use color_eyre::Result;
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("unknown instruction at {0}")]
+    UnknownInstruction(Location),
+    #[error("missing operand at {0}")]
+    MissingOperand(Location),
+    #[error("invalid operand at {0}")]
+    InvalidOperand(Location),
+    #[error("unexpected operand at {0}")]
+    UnexpectedOperand(Location),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Noop,
+    Addx(isize),
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Instruction>;
+    type Answer1 = isize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+impl StreamingSolution for Day10 {
+    fn solve_reader(reader: &mut dyn BufRead) -> Result<(isize, String)> {
+        let mut screen = Screen::default();
+        let mut x = 1;
+        screen.tick(x);
+        aoc_core::for_each_line(reader, |line| {
+            screen.tick(x);
+            if let Instruction::Addx(val) = parse_instruction(line)? {
+                x += val;
+                screen.tick(x);
+            }
+            Ok::<_, ParseError>(())
+        })?;
+        Ok((screen.strength, screen.image))
+    }
+}
+
+/// Parses one instruction per line.
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    aoc_core::lines(input.trim_end())
+        .map(parse_instruction)
+        .collect()
+}
+
+fn parse_instruction(line: Line) -> Result<Instruction, ParseError> {
+    let mut words = line.text.split_ascii_whitespace();
+    let instruction = match words.next() {
+        Some("noop") => Instruction::Noop,
+        Some("addx") => {
+            let operand = words
+                .next()
+                .ok_or_else(|| ParseError::MissingOperand(line.end()))?;
+            Instruction::Addx(
+                operand
+                    .parse()
+                    .map_err(|_| ParseError::InvalidOperand(line.locate(operand)))?,
+            )
+        }
+        Some(other) => return Err(ParseError::UnknownInstruction(line.locate(other))),
+        None => return Err(ParseError::UnknownInstruction(line.start())),
+    };
+    if let Some(rest) = words.next() {
+        return Err(ParseError::UnexpectedOperand(line.locate(rest)));
+    }
+    Ok(instruction)
+}
+
+/// Value of the X register during each cycle, starting at cycle 1.
+pub fn register_values(program: &[Instruction]) -> Vec<isize> {
+    let mut values = vec![1isize];
+    for instruction in program {
+        values.push(*values.last().unwrap());
+        if let Instruction::Addx(val) = instruction {
+            values.push(*values.last().unwrap() + val);
+        }
+    }
+    values
+}
+
+/// The answers of both parts, fed the value of the X register one cycle at a
+/// time.
+#[derive(Debug, Default)]
+struct Screen {
+    cycles: usize,
+    strength: isize,
+    image: String,
+}
+
+impl Screen {
+    fn tick(&mut self, val: isize) {
+        let idx = self.cycles;
+        self.cycles += 1;
+
+        if idx % 40 == 19 {
+            let strength = (idx + 1) as isize * val;
+            debug!(cycle = idx + 1, x = val, strength, "signal");
+            self.strength += strength;
+        }
+        if idx < 240 {
+            self.image += if [val - 1, val, val + 1].contains(&(idx as isize % 40)) {
+                "#"
+            } else {
+                " "
+            };
+            if idx % 40 == 39 && idx != 239 {
+                self.image += "\n";
+            }
+        }
+    }
+}
+
+fn run(program: &[Instruction]) -> Screen {
+    let mut screen = Screen::default();
+    for val in register_values(program) {
+        screen.tick(val);
+    }
+    screen
+}
+
+/// Sum of the signal strengths during the 20th, 60th, 100th, ... cycles.
+pub fn solve_part1(program: &[Instruction]) -> Result<isize> {
+    Ok(run(program).strength)
+}
+
+/// The CRT image drawn by the program, one line per row.
+pub fn solve_part2(program: &[Instruction]) -> Result<String> {
+    Ok(run(program).image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+
+        let program = parse(kat).unwrap();
+        assert_eq!(solve_part1(&program).unwrap(), 13140);
+        assert_eq!(
+            solve_part2(&program).unwrap(),
+            r#"##  ##  ##  ##  ##  ##  ##  ##  ##  ##  
+###   ###   ###   ###   ###   ###   ### 
+####    ####    ####    ####    ####    
+#####     #####     #####     #####     
+######      ######      ######      ####
+#######       #######       #######     "#
+        );
+    }
+    #[test]
+    fn test_stream() {
+        let kat = include_str!("../fixtures/example.txt");
+        let program = parse(kat).unwrap();
+        assert_eq!(
+            Day10::solve_reader(&mut kat.as_bytes()).unwrap(),
+            (
+                solve_part1(&program).unwrap(),
+                solve_part2(&program).unwrap()
+            )
+        );
+
+        let error = Day10::solve_reader(&mut "noop\naddx".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ParseError>(),
+            parse("noop\naddx").err().as_ref()
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("noop\nmulx 3").unwrap_err(),
+            ParseError::UnknownInstruction(Location::new(2, 1, "mulx 3"))
+        );
+        assert_eq!(
+            parse("noop\naddx").unwrap_err(),
+            ParseError::MissingOperand(Location::new(2, 5, "addx"))
+        );
+        assert_eq!(
+            parse("noop\naddx three").unwrap_err(),
+            ParseError::InvalidOperand(Location::new(2, 6, "addx three"))
+        );
+        assert_eq!(
+            parse("noop\nnoop 1").unwrap_err(),
+            ParseError::UnexpectedOperand(Location::new(2, 6, "noop 1"))
+        );
+    }
+}
@@ -0,0 +1,390 @@
+//! Advent of Code 2022 Day 11: Monkey in the Middle
+
+use aoc_core::{Location, Solution};
+use color_eyre::{eyre::eyre, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, multispace0, space0, space1, u64},
+    combinator::{consumed, eof, map, map_res, value},
+    multi::separated_list0,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid monkey description at {0}")]
+    Syntax(Location),
+    #[error("monkeys must be numbered 0, 1, 2, ... but found {0}")]
+    UnexpectedMonkey(Location),
+    #[error("test for divisibility by zero in the monkey at {0}")]
+    ZeroDivisor(Location),
+    #[error("throw to the monkey itself or one that doesn't exist in the monkey at {0}")]
+    InvalidTarget(Location),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Old,
+    Number(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Multiply,
+}
+
+/// The right-hand side of an operation like `new = old * 19`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expression {
+    pub lhs: Operand,
+    pub operator: Operator,
+    pub rhs: Operand,
+}
+
+impl Expression {
+    /// The new worry level of an item, which can't overflow as both operands
+    /// fit in a `u64`.
+    pub fn eval(&self, old: u64) -> u128 {
+        let operand = |operand| match operand {
+            Operand::Old => old as u128,
+            Operand::Number(x) => x as u128,
+        };
+        match self.operator {
+            Operator::Add => operand(self.lhs) + operand(self.rhs),
+            Operator::Multiply => operand(self.lhs) * operand(self.rhs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    /// Worry levels of the items the monkey starts with.
+    pub items: Vec<u64>,
+    pub operation: Expression,
+    /// The monkey throws to `if_true` if the new worry level is divisible by
+    /// this, to `if_false` otherwise.
+    pub divisor: u64,
+    pub if_true: usize,
+    pub if_false: usize,
+}
+
+fn parse_usize(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, str::parse::<usize>)(input)
+}
+
+fn parse_operand(input: &str) -> IResult<&str, Operand> {
+    alt((value(Operand::Old, tag("old")), map(u64, Operand::Number)))(input)
+}
+
+/// Parses an expression like `old * 19`.
+pub fn parse_expression(input: &str) -> IResult<&str, Expression> {
+    let operator = alt((
+        value(Operator::Add, char('+')),
+        value(Operator::Multiply, char('*')),
+    ));
+    map(
+        tuple((
+            parse_operand,
+            delimited(space1, operator, space1),
+            parse_operand,
+        )),
+        |(lhs, operator, rhs)| Expression { lhs, operator, rhs },
+    )(input)
+}
+
+// a line starting with `label` after any indentation, the last one may lack
+// its line ending
+fn field<'a, O>(
+    label: &'static str,
+    content: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    delimited(
+        pair(space0, tag(label)),
+        content,
+        pair(space0, alt((line_ending, eof))),
+    )
+}
+
+/// Parses the block describing a monkey, returning its number and the monkey.
+pub fn parse_monkey(input: &str) -> IResult<&str, (usize, Monkey)> {
+    let (input, number) = field("Monkey ", terminated(parse_usize, char(':')))(input)?;
+    let (input, items) = field(
+        "Starting items:",
+        preceded(space0, separated_list0(pair(char(','), space0), u64)),
+    )(input)?;
+    let (input, operation) = field("Operation: new = ", parse_expression)(input)?;
+    let (input, divisor) = field("Test: divisible by ", u64)(input)?;
+    let (input, if_true) = field("If true: throw to monkey ", parse_usize)(input)?;
+    let (input, if_false) = field("If false: throw to monkey ", parse_usize)(input)?;
+
+    Ok((
+        input,
+        (
+            number,
+            Monkey {
+                items,
+                operation,
+                divisor,
+                if_true,
+                if_false,
+            },
+        ),
+    ))
+}
+
+/// Parses the monkeys, which are separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let offset = |rest: &str| input.len() - rest.len();
+    let syntax = |e: nom::Err<nom::error::Error<&str>>| {
+        let rest = match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.input,
+            nom::Err::Incomplete(_) => "",
+        };
+        ParseError::Syntax(aoc_core::locate(input, offset(rest)))
+    };
+
+    let mut monkeys = Vec::new();
+    // the offset of the first line of each monkey, to locate the errors in it
+    let mut starts = Vec::new();
+    let mut rest = input;
+    loop {
+        let (next, (block, (number, monkey))) = consumed(parse_monkey)(rest).map_err(syntax)?;
+        if number != monkeys.len() {
+            return Err(ParseError::UnexpectedMonkey(aoc_core::locate(
+                input,
+                offset(block.trim_start()),
+            )));
+        }
+        monkeys.push(monkey);
+        starts.push(offset(block.trim_start()));
+
+        let (next, _) = multispace0::<_, nom::error::Error<_>>(next).map_err(syntax)?;
+        if next.is_empty() {
+            break;
+        }
+        rest = next;
+    }
+
+    for (idx, monkey) in monkeys.iter().enumerate() {
+        let location = || aoc_core::locate(input, starts[idx]);
+        if monkey.divisor == 0 {
+            return Err(ParseError::ZeroDivisor(location()));
+        }
+        if [monkey.if_true, monkey.if_false]
+            .iter()
+            .any(|&target| target == idx || target >= monkeys.len())
+        {
+            return Err(ParseError::InvalidTarget(location()));
+        }
+    }
+    Ok(monkeys)
+}
+
+/// How the worry levels are kept in check after each inspection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relief {
+    /// Divided by three, as the item wasn't damaged.
+    Divide,
+    /// Reduced modulo the least common multiple of the divisors, which doesn't
+    /// change where any monkey throws an item.
+    Modulo,
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The monkeys passing items around, round by round.
+#[derive(Debug, Clone)]
+pub struct Troop<'a> {
+    monkeys: &'a [Monkey],
+    items: Vec<Vec<u64>>,
+    inspections: Vec<usize>,
+    relief: Relief,
+    modulus: u64,
+}
+
+impl<'a> Troop<'a> {
+    pub fn new(monkeys: &'a [Monkey], relief: Relief) -> Result<Self> {
+        let modulus = monkeys.iter().try_fold(1u64, |lcm, monkey| {
+            (lcm / gcd(lcm, monkey.divisor))
+                .checked_mul(monkey.divisor)
+                .ok_or_else(|| eyre!("the divisors have no common multiple within 64 bits"))
+        })?;
+        Ok(Troop {
+            monkeys,
+            items: monkeys.iter().map(|monkey| monkey.items.clone()).collect(),
+            inspections: vec![0; monkeys.len()],
+            relief,
+            modulus,
+        })
+    }
+
+    /// Lets each monkey in turn inspect and throw all items it holds.
+    pub fn round(&mut self) -> Result<()> {
+        for (idx, monkey) in self.monkeys.iter().enumerate() {
+            let items = std::mem::take(&mut self.items[idx]);
+            self.inspections[idx] += items.len();
+            for old in items {
+                let new = monkey.operation.eval(old);
+                let new = match self.relief {
+                    Relief::Divide => u64::try_from(new / 3)
+                        .map_err(|_| eyre!("the worry level of an item overflows"))?,
+                    Relief::Modulo => (new % self.modulus as u128) as u64,
+                };
+                let target = if new % monkey.divisor == 0 {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                self.items[target].push(new);
+            }
+        }
+        Ok(())
+    }
+
+    /// The worry levels of the items each monkey holds.
+    pub fn items(&self) -> &[Vec<u64>] {
+        &self.items
+    }
+
+    /// Number of items each monkey inspected so far.
+    pub fn inspections(&self) -> &[usize] {
+        &self.inspections
+    }
+
+    /// Product of the inspections of the two most active monkeys.
+    pub fn monkey_business(&self) -> Result<usize> {
+        let mut inspections = self.inspections.clone();
+        inspections.sort_unstable_by(|a, b| b.cmp(a));
+        match inspections[..] {
+            [first, second, ..] => Ok(first * second),
+            _ => Err(eyre!("there must be at least two monkeys")),
+        }
+    }
+}
+
+/// The troop after `rounds` rounds.
+pub fn simulate(monkeys: &[Monkey], rounds: usize, relief: Relief) -> Result<Troop<'_>> {
+    let mut troop = Troop::new(monkeys, relief)?;
+    for round in 1..=rounds {
+        troop.round()?;
+        debug!(round, inspections = ?troop.inspections(), "round");
+    }
+    Ok(troop)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<Monkey>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+/// Monkey business after 20 rounds, dividing worry levels by three.
+pub fn solve_part1(monkeys: &[Monkey]) -> Result<usize> {
+    simulate(monkeys, 20, Relief::Divide)?.monkey_business()
+}
+
+/// Monkey business after 10000 rounds without relief.
+pub fn solve_part2(monkeys: &[Monkey]) -> Result<usize> {
+    simulate(monkeys, 10000, Relief::Modulo)?.monkey_business()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let monkeys = parse(kat).unwrap();
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(
+            monkeys[2].operation,
+            Expression {
+                lhs: Operand::Old,
+                operator: Operator::Multiply,
+                rhs: Operand::Old
+            }
+        );
+        assert_eq!(
+            solve_part1(&monkeys).unwrap().to_string(),
+            include_str!("../fixtures/answer1.txt").trim_end()
+        );
+        assert_eq!(
+            solve_part2(&monkeys).unwrap().to_string(),
+            include_str!("../fixtures/answer2.txt").trim_end()
+        );
+
+        let troop = simulate(&monkeys, 1, Relief::Divide).unwrap();
+        assert_eq!(
+            troop.items(),
+            [
+                vec![20, 23, 27, 26],
+                vec![2080, 25, 167, 207, 401, 1046],
+                vec![],
+                vec![]
+            ]
+        );
+        let troop = simulate(&monkeys, 20, Relief::Modulo).unwrap();
+        assert_eq!(troop.inspections(), [99, 97, 8, 103]);
+    }
+    #[test]
+    fn test_errors() {
+        let monkey = |number, divisor, target| {
+            format!(
+                "Monkey {}:\n  Starting items: 1\n  Operation: new = old + 1\n  \
+                 Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                 If false: throw to monkey {}\n",
+                number,
+                divisor,
+                target,
+                (number + 1) % 2
+            )
+        };
+
+        assert_eq!(
+            parse("Monkey 0:\n  Starting items: 1\n  Operation: new = old - 1\n").unwrap_err(),
+            ParseError::Syntax(Location::new(3, 24, "  Operation: new = old - 1"))
+        );
+        assert_eq!(
+            parse(&format!("{}\n{}", monkey(0, 2, 1), monkey(2, 2, 1))).unwrap_err(),
+            ParseError::UnexpectedMonkey(Location::new(8, 1, "Monkey 2:"))
+        );
+        assert_eq!(
+            parse(&format!("{}\n{}", monkey(0, 2, 1), monkey(1, 0, 0))).unwrap_err(),
+            ParseError::ZeroDivisor(Location::new(8, 1, "Monkey 1:"))
+        );
+        assert_eq!(
+            parse(&format!("{}\n{}", monkey(0, 2, 1), monkey(1, 3, 2))).unwrap_err(),
+            ParseError::InvalidTarget(Location::new(8, 1, "Monkey 1:"))
+        );
+        assert_eq!(
+            parse(&monkey(0, 2, 1)).unwrap_err(),
+            ParseError::InvalidTarget(Location::new(1, 1, "Monkey 0:"))
+        );
+    }
+}
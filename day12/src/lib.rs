@@ -0,0 +1,300 @@
+//! Advent of Code 2022 Day 12: Hill Climbing Algorithm
+
+use std::collections::VecDeque;
+
+use aoc_core::{Location, Solution};
+use color_eyre::{eyre::eyre, Result};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid elevation at {0}")]
+    InvalidElevation(Location),
+    #[error("row of a different width than the first at {0}")]
+    RaggedRow(Location),
+    #[error("second start position at {0}")]
+    DuplicateStart(Location),
+    #[error("second best signal position at {0}")]
+    DuplicateEnd(Location),
+    #[error("no start position marked with S")]
+    MissingStart,
+    #[error("no best signal position marked with E")]
+    MissingEnd,
+}
+
+/// The row and column of a square.
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heightmap {
+    /// Elevations from 0 for `a` to 25 for `z`, as `heights[row][column]`.
+    pub heights: Vec<Vec<u8>>,
+    /// The current position, at elevation `a`.
+    pub start: Pos,
+    /// The position with the best signal, at elevation `z`.
+    pub end: Pos,
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Heightmap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+/// Parses the grid of elevations `a` to `z`, with `S` marking the start and
+/// `E` the best signal.
+pub fn parse(input: &str) -> Result<Heightmap, ParseError> {
+    let mut heights: Vec<Vec<u8>> = Vec::new();
+    let (mut start, mut end) = (None, None);
+    for (row, line) in aoc_core::lines(input.trim_end()).enumerate() {
+        let mut elevations = Vec::new();
+        for (idx, x) in line.text.char_indices() {
+            let location = || line.locate(&line.text[idx..idx + x.len_utf8()]);
+            let pos = (row, elevations.len());
+            let elevation = match x {
+                'S' if start.is_some() => return Err(ParseError::DuplicateStart(location())),
+                'E' if end.is_some() => return Err(ParseError::DuplicateEnd(location())),
+                'S' => {
+                    start = Some(pos);
+                    b'a'
+                }
+                'E' => {
+                    end = Some(pos);
+                    b'z'
+                }
+                'a'..='z' => x as u8,
+                _ => return Err(ParseError::InvalidElevation(location())),
+            };
+            elevations.push(elevation - b'a');
+        }
+        if let Some(first) = heights.first() {
+            if elevations.len() != first.len() {
+                let column = elevations.len().min(first.len());
+                let offset = line
+                    .text
+                    .char_indices()
+                    .nth(column)
+                    .map_or(line.text.len(), |x| x.0);
+                return Err(ParseError::RaggedRow(line.locate(&line.text[offset..])));
+            }
+        }
+        heights.push(elevations);
+    }
+    Ok(Heightmap {
+        heights,
+        start: start.ok_or(ParseError::MissingStart)?,
+        end: end.ok_or(ParseError::MissingEnd)?,
+    })
+}
+
+/// Finds a shortest path through the grid with a breadth-first search from
+/// all `sources` at once, moving to the adjacent squares for which
+/// `can_step(from, to)` allows the change in elevation.
+///
+/// Returns the squares on the path, from one of the sources to the first
+/// square that `is_goal`, or `None` if there is no such square in reach.
+pub fn bfs(
+    heights: &[Vec<u8>],
+    sources: impl IntoIterator<Item = Pos>,
+    can_step: impl Fn(u8, u8) -> bool,
+    is_goal: impl Fn(Pos) -> bool,
+) -> Option<Vec<Pos>> {
+    // where each square was first reached from, the sources from themselves
+    let mut previous: Vec<Vec<Option<Pos>>> =
+        heights.iter().map(|row| vec![None; row.len()]).collect();
+    let mut queue = VecDeque::new();
+    for pos in sources {
+        if previous[pos.0][pos.1].is_none() {
+            previous[pos.0][pos.1] = Some(pos);
+            queue.push_back(pos);
+        }
+    }
+
+    while let Some(pos) = queue.pop_front() {
+        if is_goal(pos) {
+            let mut path = vec![pos];
+            let mut pos = pos;
+            while let Some(prev) = previous[pos.0][pos.1].filter(|&prev| prev != pos) {
+                path.push(prev);
+                pos = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let (row, column) = pos;
+        let neighbours = [
+            row.checked_sub(1).map(|row| (row, column)),
+            Some((row + 1, column)).filter(|&(row, _)| row < heights.len()),
+            column.checked_sub(1).map(|column| (row, column)),
+            Some((row, column + 1)).filter(|&(_, column)| column < heights[row].len()),
+        ];
+        for next in neighbours.into_iter().flatten() {
+            if previous[next.0][next.1].is_none()
+                && can_step(heights[row][column], heights[next.0][next.1])
+            {
+                previous[next.0][next.1] = Some(pos);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+impl Heightmap {
+    /// A shortest path from the start to the best signal, climbing at most one
+    /// elevation per step.
+    pub fn shortest_climb(&self) -> Option<Vec<Pos>> {
+        bfs(
+            &self.heights,
+            [self.start],
+            |from, to| to <= from + 1,
+            |pos| pos == self.end,
+        )
+    }
+
+    /// A shortest path from any square at elevation `a` to the best signal.
+    ///
+    /// It searches backwards from the best signal, so that a single search
+    /// finds the closest of all those squares.
+    pub fn shortest_hike(&self) -> Option<Vec<Pos>> {
+        let mut path = bfs(
+            &self.heights,
+            [self.end],
+            |from, to| from <= to + 1,
+            |(row, column)| self.heights[row][column] == 0,
+        )?;
+        path.reverse();
+        Some(path)
+    }
+
+    /// Draws the path over the grid like the puzzle does: each square on it
+    /// shows the direction of the next step as `^`, `v`, `<` or `>`, the last
+    /// one is `E` and all others are `.`.
+    pub fn render(&self, path: &[Pos]) -> String {
+        let mut grid: Vec<Vec<char>> = self
+            .heights
+            .iter()
+            .map(|row| vec!['.'; row.len()])
+            .collect();
+        for step in path.windows(2) {
+            let ((row, column), next) = (step[0], step[1]);
+            grid[row][column] = if next.0 < row {
+                '^'
+            } else if next.0 > row {
+                'v'
+            } else if next.1 < column {
+                '<'
+            } else {
+                '>'
+            };
+        }
+        if let Some(&(row, column)) = path.last() {
+            grid[row][column] = 'E';
+        }
+        grid.into_iter()
+            .map(|row| row.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Fewest steps from the start to the best signal.
+pub fn solve_part1(map: &Heightmap) -> Result<usize> {
+    let path = map
+        .shortest_climb()
+        .ok_or_else(|| eyre!("the best signal can't be reached from the start"))?;
+    Ok(path.len() - 1)
+}
+
+/// Fewest steps from any square at elevation `a` to the best signal.
+pub fn solve_part2(map: &Heightmap) -> Result<usize> {
+    let path = map
+        .shortest_hike()
+        .ok_or_else(|| eyre!("the best signal can't be reached from elevation a"))?;
+    Ok(path.len() - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let map = parse(kat).unwrap();
+        assert_eq!(map.start, (0, 0));
+        assert_eq!(map.end, (2, 5));
+        assert_eq!(
+            solve_part1(&map).unwrap().to_string(),
+            include_str!("../fixtures/answer1.txt").trim_end()
+        );
+        assert_eq!(
+            solve_part2(&map).unwrap().to_string(),
+            include_str!("../fixtures/answer2.txt").trim_end()
+        );
+    }
+    #[test]
+    fn test_render() {
+        let map = parse(include_str!("../fixtures/example.txt")).unwrap();
+
+        let climb = map.shortest_climb().unwrap();
+        assert_eq!(climb.first(), Some(&map.start));
+        assert_eq!(climb.last(), Some(&map.end));
+        // any shortest path will do, but it must only take allowed steps
+        for step in climb.windows(2) {
+            let ((r1, c1), (r2, c2)) = (step[0], step[1]);
+            assert_eq!(r1.abs_diff(r2) + c1.abs_diff(c2), 1);
+            assert!(map.heights[r2][c2] <= map.heights[r1][c1] + 1);
+        }
+        let rendered = map.render(&climb);
+        assert_eq!(rendered.matches(['^', 'v', '<', '>']).count(), 31);
+        assert_eq!(rendered.lines().nth(2).unwrap().find('E'), Some(5));
+
+        let hike = map.shortest_hike().unwrap();
+        assert_eq!(
+            map.render(&hike),
+            "...v<<<<\n\
+             ...vv<<^\n\
+             ...v>E^^\n\
+             .>v>>>^^\n\
+             >^>>>>>^"
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("Sab\naBc\nbcE").unwrap_err(),
+            ParseError::InvalidElevation(Location::new(2, 2, "aBc"))
+        );
+        assert_eq!(
+            parse("Sab\nab\nbcE").unwrap_err(),
+            ParseError::RaggedRow(Location::new(2, 3, "ab"))
+        );
+        assert_eq!(
+            parse("Sab\naSE").unwrap_err(),
+            ParseError::DuplicateStart(Location::new(2, 2, "aSE"))
+        );
+        assert_eq!(
+            parse("SEb\nabE").unwrap_err(),
+            ParseError::DuplicateEnd(Location::new(2, 3, "abE"))
+        );
+        assert_eq!(parse("abc\nabE").unwrap_err(), ParseError::MissingStart);
+        assert_eq!(parse("\n").unwrap_err(), ParseError::MissingStart);
+        assert_eq!(parse("Sbc\nabc").unwrap_err(), ParseError::MissingEnd);
+    }
+}
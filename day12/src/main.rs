@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use aoc_core::Format;
+use color_eyre::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "day12", about = "Advent of Code Day 12")]
+struct Args {
+    /// Input files to solve, `-` for stdin
+    #[structopt(parse(from_os_str), default_value = "./input")]
+    input_files: Vec<PathBuf>,
+    #[structopt(long, default_value = "text", possible_values = &["text", "json"])]
+    format: Format,
+    /// Log the solver's progress to stderr, more with -vv; RUST_LOG takes precedence
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: u8,
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    aoc_core::init_tracing(args.verbose);
+
+    aoc_core::run::<day12::Day12>(&args.input_files, args.format)
+}
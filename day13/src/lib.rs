@@ -0,0 +1,312 @@
+//! Advent of Code 2022 Day 13: Distress Signal
+
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use aoc_core::{Line, Location, Solution};
+use color_eyre::{eyre::eyre, Result};
+use nom::{
+    branch::alt,
+    character::complete::{char, u32},
+    combinator::{cut, map, opt},
+    error::ErrorKind,
+    multi::many0,
+    sequence::{delimited, pair, preceded},
+    IResult,
+};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid packet at {0}")]
+    Syntax(Location),
+    #[error("unexpected input after the packet at {0}")]
+    TrailingInput(Location),
+    #[error("packet nested too deeply at {0}")]
+    TooDeep(Location),
+    #[error("pair without a second packet at {0}")]
+    MissingPacket(Location),
+    #[error("third packet in a pair at {0}")]
+    UnexpectedPacket(Location),
+}
+
+/// Packets nested deeper than this are rejected, so that parsing and
+/// comparing them can't overflow the stack.
+pub const MAX_DEPTH: usize = 64;
+
+/// A packet, like `[1,[2,[3,4]]]`, or a value in one.
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    /// Integers compare by value and lists element by element, the shorter
+    /// list first if one runs out. An integer compared with a list is treated
+    /// as a list of just that integer.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(a), Packet::Integer(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            (Packet::Integer(_), Packet::List(b)) => std::slice::from_ref(self).cmp(b),
+            (Packet::List(a), Packet::Integer(_)) => a.as_slice().cmp(std::slice::from_ref(other)),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// consistent with the ordering, so `2`, `[2]` and `[[2]]` are all equal
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Integer(x) => write!(f, "{}", x),
+            Packet::List(packets) => {
+                write!(f, "[")?;
+                for (idx, packet) in packets.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", packet)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+fn parse_nested(input: &str, depth: usize) -> IResult<&str, Packet> {
+    if depth > MAX_DEPTH {
+        return Err(nom::Err::Failure(nom::error::Error::new(
+            input,
+            ErrorKind::TooLarge,
+        )));
+    }
+    let element = move |input| parse_nested(input, depth + 1);
+    alt((
+        map(u32, Packet::Integer),
+        map(
+            delimited(
+                char('['),
+                // after a comma there has to be another element
+                opt(pair(element, many0(preceded(char(','), cut(element))))),
+                char(']'),
+            ),
+            |list| {
+                let (first, rest) = list.unzip();
+                Packet::List(
+                    first
+                        .into_iter()
+                        .chain(rest.into_iter().flatten())
+                        .collect(),
+                )
+            },
+        ),
+    ))(input)
+}
+
+/// Parses a packet, or an integer within one.
+pub fn parse_packet(input: &str) -> IResult<&str, Packet> {
+    parse_nested(input, 0)
+}
+
+fn parse_line(line: &Line) -> Result<Packet, ParseError> {
+    let (rest, packet) = parse_packet(line.text).map_err(|e| match e {
+        nom::Err::Failure(e) if e.code == ErrorKind::TooLarge => {
+            ParseError::TooDeep(line.locate(e.input))
+        }
+        nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::Syntax(line.locate(e.input)),
+        nom::Err::Incomplete(_) => ParseError::Syntax(line.end()),
+    })?;
+    if !rest.is_empty() {
+        return Err(ParseError::TrailingInput(line.locate(rest)));
+    }
+    Ok(packet)
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_line(&Line { number: 1, text: s })
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<(Packet, Packet)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+/// Parses pairs of packets, one per line, separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+    let mut pairs = Vec::new();
+    // the first packet of a pair and the end of its line, until the second
+    let mut first: Option<(Packet, Location)> = None;
+    let mut paired = false;
+    for line in aoc_core::lines(input.trim_end()) {
+        if line.text.trim().is_empty() {
+            if let Some((_, end)) = first.take() {
+                return Err(ParseError::MissingPacket(end));
+            }
+            paired = false;
+            continue;
+        }
+        if paired {
+            return Err(ParseError::UnexpectedPacket(line.start()));
+        }
+
+        let packet = parse_line(&line)?;
+        match first.take() {
+            Some((left, _)) => {
+                pairs.push((left, packet));
+                paired = true;
+            }
+            None => first = Some((packet, line.end())),
+        }
+    }
+    if let Some((_, end)) = first {
+        return Err(ParseError::MissingPacket(end));
+    }
+    Ok(pairs)
+}
+
+/// Sum of the indices, starting at 1, of the pairs in the right order.
+pub fn solve_part1(pairs: &[(Packet, Packet)]) -> Result<usize> {
+    Ok(pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (left, right))| left < right)
+        .map(|(idx, _)| idx + 1)
+        .sum())
+}
+
+/// Product of the indices, starting at 1, of the divider packets `[[2]]` and
+/// `[[6]]` once they are sorted in with all other packets.
+pub fn solve_part2(pairs: &[(Packet, Packet)]) -> Result<usize> {
+    let dividers: [Packet; 2] = ["[[2]]".parse()?, "[[6]]".parse()?];
+    let mut packets: Vec<&Packet> = pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .chain(&dividers)
+        .collect();
+    packets.sort();
+
+    dividers.iter().try_fold(1, |key, divider| {
+        // packets equal to a divider, like `[2]`, may come before it
+        let idx = packets
+            .iter()
+            .position(|&packet| std::ptr::eq(packet, divider))
+            .ok_or_else(|| eyre!("divider packet {} not found", divider))?;
+        Ok(key * (idx + 1))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(s: &str) -> Packet {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let pairs = parse(kat).unwrap();
+        assert_eq!(pairs.len(), 8);
+        assert_eq!(
+            solve_part1(&pairs).unwrap().to_string(),
+            include_str!("../fixtures/answer1.txt").trim_end()
+        );
+        assert_eq!(
+            solve_part2(&pairs).unwrap().to_string(),
+            include_str!("../fixtures/answer2.txt").trim_end()
+        );
+    }
+    #[test]
+    fn test_compare() {
+        // integers by value
+        assert!(packet("3") < packet("5"));
+        assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+        assert_eq!(packet("7"), packet("7"));
+        // lists element by element, the first difference decides
+        assert!(packet("[[4,4],4,4]") < packet("[[4,4],4,4,4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        assert!(packet("[1,[2,[3,[4,[5,6,7]]]],8,9]") > packet("[1,[2,[3,[4,[5,6,0]]]],8,9]"));
+        // the list that runs out first comes first
+        assert!(packet("[7,7,7]") < packet("[7,7,7,7]"));
+        assert!(packet("[]") < packet("[3]"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        // an integer compared to a list is a list of just that integer
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[2]") < packet("[[2,1]]"));
+        assert!(packet("[[8]]") > packet("[7,9]"));
+        assert_eq!(packet("2"), packet("[[2]]"));
+    }
+    #[test]
+    fn test_display() {
+        for s in ["[]", "[[1],[2,3,4]]", "[1,[2,[3,[4,[5,6,7]]]],8,9]", "10"] {
+            assert_eq!(packet(s).to_string(), s);
+        }
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("[1,2]\n[1,,2]\n").unwrap_err(),
+            ParseError::Syntax(Location::new(2, 4, "[1,,2]"))
+        );
+        assert_eq!(
+            parse("[1,2]\n[1] [2]\n").unwrap_err(),
+            ParseError::TrailingInput(Location::new(2, 4, "[1] [2]"))
+        );
+        assert_eq!(
+            parse("[1,2]\n\n[3]\n").unwrap_err(),
+            ParseError::MissingPacket(Location::new(1, 6, "[1,2]"))
+        );
+        assert_eq!(
+            parse("[1,2]\n[3]\n[4]\n").unwrap_err(),
+            ParseError::UnexpectedPacket(Location::new(3, 1, "[4]"))
+        );
+        assert_eq!(
+            parse("[1,2]\n[3]\n\n[4]").unwrap_err(),
+            ParseError::MissingPacket(Location::new(4, 4, "[4]"))
+        );
+
+        let deep = format!("{}{}", "[".repeat(100), "]".repeat(100));
+        assert!(matches!(
+            parse(&format!("[]\n{}", deep)).unwrap_err(),
+            ParseError::TooDeep(Location {
+                line: 2,
+                column: 66,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,328 @@
+//! Advent of Code 2022 Day 14: Regolith Reservoir
+
+use std::fmt::Display;
+
+use aoc_core::{Line, Location, Solution};
+use color_eyre::{eyre::eyre, Result};
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid point at {0}")]
+    InvalidPoint(Location),
+    #[error("path going diagonally at {0}")]
+    DiagonalSegment(Location),
+}
+
+/// The x and y coordinates of a tile, with y growing downwards.
+pub type Pos = (u32, u32);
+
+/// Where the sand pours into the cave.
+pub const SOURCE: Pos = (500, 0);
+
+/// Caves with more tiles than this are rejected rather than allocated.
+pub const MAX_TILES: usize = 1 << 26;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// The slice of the cave that sand can reach, stored densely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cave {
+    tiles: Vec<Tile>,
+    /// The x coordinate of the leftmost column.
+    left: i64,
+    width: usize,
+    height: usize,
+}
+
+impl Cave {
+    /// A cave with rock along the `paths`, and with `floor` an infinite floor
+    /// of rock two below the lowest path.
+    ///
+    /// Without a floor it spans the paths and the source, beyond which sand
+    /// falls into the abyss. With one it is wide enough that sand never gets
+    /// past its edges.
+    pub fn new(paths: &[Vec<Pos>], floor: bool) -> Result<Self> {
+        let points = || paths.iter().flatten().chain([&SOURCE]);
+        let mut bottom = points().map(|&(_, y)| y as i64).max().unwrap();
+        let mut left = points().map(|&(x, _)| x as i64).min().unwrap();
+        let mut right = points().map(|&(x, _)| x as i64).max().unwrap();
+        if floor {
+            // sand spreads at most one column per row it falls
+            bottom += 2;
+            left = left.min(SOURCE.0 as i64 - bottom);
+            right = right.max(SOURCE.0 as i64 + bottom);
+        }
+
+        let (width, height) = ((right - left + 1) as usize, (bottom + 1) as usize);
+        let tiles = width
+            .checked_mul(height)
+            .filter(|&tiles| tiles <= MAX_TILES)
+            .ok_or_else(|| eyre!("the cave is too large, {} by {} tiles", width, height))?;
+        let mut cave = Cave {
+            tiles: vec![Tile::Air; tiles],
+            left,
+            width,
+            height,
+        };
+
+        for path in paths {
+            let segments = path.windows(2).map(|segment| (segment[0], segment[1]));
+            // a path of a single point is a single rock
+            let point = path.first().filter(|_| path.len() == 1);
+            for ((x1, y1), (x2, y2)) in segments.chain(point.map(|&point| (point, point))) {
+                for x in x1.min(x2)..=x1.max(x2) {
+                    for y in y1.min(y2)..=y1.max(y2) {
+                        let idx = cave.index((x as i64, y as i64)).unwrap();
+                        cave.tiles[idx] = Tile::Rock;
+                    }
+                }
+            }
+        }
+        if floor {
+            let start = (height - 1) * width;
+            cave.tiles[start..].fill(Tile::Rock);
+        }
+        Ok(cave)
+    }
+
+    fn index(&self, (x, y): (i64, i64)) -> Option<usize> {
+        let column = x - self.left;
+        if column < 0 || column >= self.width as i64 || y < 0 || y >= self.height as i64 {
+            return None;
+        }
+        Some(y as usize * self.width + column as usize)
+    }
+
+    /// The tile at `pos`, or `None` if it is outside of the cave.
+    pub fn get(&self, (x, y): Pos) -> Option<Tile> {
+        self.index((x as i64, y as i64)).map(|idx| self.tiles[idx])
+    }
+
+    /// Pours sand in from the source until a unit falls into the abyss or the
+    /// source is blocked, returning the number of units that came to rest.
+    pub fn pour(&mut self) -> usize {
+        let source = (SOURCE.0 as i64, SOURCE.1 as i64);
+        if self.get(SOURCE) != Some(Tile::Air) {
+            return 0;
+        }
+
+        let mut count = 0;
+        // the way the last unit fell, the next one falls the same way up to
+        // where it came to rest
+        let mut trail = vec![source];
+        while let Some(&(x, y)) = trail.last() {
+            let mut next = None;
+            for pos in [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)] {
+                match self.index(pos) {
+                    // nothing below but the abyss
+                    None => return count,
+                    Some(idx) if self.tiles[idx] == Tile::Air => {
+                        next = Some(pos);
+                        break;
+                    }
+                    Some(_) => {}
+                }
+            }
+            match next {
+                Some(pos) => trail.push(pos),
+                None => {
+                    let idx = self.index((x, y)).unwrap();
+                    self.tiles[idx] = Tile::Sand;
+                    count += 1;
+                    trail.pop();
+                }
+            }
+        }
+        count
+    }
+}
+
+impl Display for Cave {
+    /// Draws the cave like the puzzle does: `#` for rock, `o` for sand, `+`
+    /// for the source if nothing covers it and `.` for air.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.tiles.chunks(self.width).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (column, tile) in row.iter().enumerate() {
+                let pos = (self.left + column as i64, y as i64);
+                let tile = match tile {
+                    Tile::Air if pos == (SOURCE.0 as i64, SOURCE.1 as i64) => '+',
+                    Tile::Air => '.',
+                    Tile::Rock => '#',
+                    Tile::Sand => 'o',
+                };
+                write!(f, "{}", tile)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Vec<Vec<Pos>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+fn parse_point(line: &Line, text: &str) -> Result<Pos, ParseError> {
+    let invalid = || ParseError::InvalidPoint(line.locate(text));
+    let (x, y) = text.split_once(',').ok_or_else(invalid)?;
+    Ok((
+        x.parse().map_err(|_| invalid())?,
+        y.parse().map_err(|_| invalid())?,
+    ))
+}
+
+/// Parses one path of rock per line, like `498,4 -> 498,6 -> 496,6`.
+pub fn parse(input: &str) -> Result<Vec<Vec<Pos>>, ParseError> {
+    aoc_core::lines(input.trim_end())
+        .map(|line| {
+            let mut path: Vec<Pos> = Vec::new();
+            for text in line.text.split(" -> ") {
+                let point = parse_point(&line, text)?;
+                if let Some(&(x, y)) = path.last() {
+                    if x != point.0 && y != point.1 {
+                        return Err(ParseError::DiagonalSegment(line.locate(text)));
+                    }
+                }
+                path.push(point);
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Units of sand that come to rest before the rest falls into the abyss.
+pub fn solve_part1(paths: &[Vec<Pos>]) -> Result<usize> {
+    let mut cave = Cave::new(paths, false)?;
+    let sand = cave.pour();
+    debug!(sand, "cave without a floor:\n{}", cave);
+    Ok(sand)
+}
+
+/// Units of sand that come to rest on the floor until the source is blocked.
+pub fn solve_part2(paths: &[Vec<Pos>]) -> Result<usize> {
+    let mut cave = Cave::new(paths, true)?;
+    let sand = cave.pour();
+    debug!(sand, "cave with a floor:\n{}", cave);
+    Ok(sand)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let paths = parse(kat).unwrap();
+        assert_eq!(paths[0], [(498, 4), (498, 6), (496, 6)]);
+        assert_eq!(
+            solve_part1(&paths).unwrap().to_string(),
+            include_str!("../fixtures/answer1.txt").trim_end()
+        );
+        assert_eq!(
+            solve_part2(&paths).unwrap().to_string(),
+            include_str!("../fixtures/answer2.txt").trim_end()
+        );
+
+        // nothing to stop the sand but the floor
+        assert_eq!(solve_part1(&[]).unwrap(), 0);
+        assert_eq!(solve_part2(&[]).unwrap(), 4);
+    }
+    #[test]
+    fn test_render() {
+        let paths = parse(include_str!("../fixtures/example.txt")).unwrap();
+
+        let mut cave = Cave::new(&paths, false).unwrap();
+        assert_eq!(
+            cave.to_string(),
+            "......+...\n\
+             ..........\n\
+             ..........\n\
+             ..........\n\
+             ....#...##\n\
+             ....#...#.\n\
+             ..###...#.\n\
+             ........#.\n\
+             ........#.\n\
+             #########."
+        );
+        cave.pour();
+        assert_eq!(
+            cave.to_string(),
+            "......+...\n\
+             ..........\n\
+             ......o...\n\
+             .....ooo..\n\
+             ....#ooo##\n\
+             ...o#ooo#.\n\
+             ..###ooo#.\n\
+             ....oooo#.\n\
+             .o.ooooo#.\n\
+             #########."
+        );
+        assert_eq!(cave.get((495, 8)), Some(Tile::Sand));
+        assert_eq!(cave.get((493, 8)), None);
+
+        let mut cave = Cave::new(&paths, true).unwrap();
+        cave.pour();
+        assert_eq!(
+            cave.to_string(),
+            "...........o...........\n\
+             ..........ooo..........\n\
+             .........ooooo.........\n\
+             ........ooooooo........\n\
+             .......oo#ooo##o.......\n\
+             ......ooo#ooo#ooo......\n\
+             .....oo###ooo#oooo.....\n\
+             ....oooo.oooo#ooooo....\n\
+             ...oooooooooo#oooooo...\n\
+             ..ooo#########ooooooo..\n\
+             .ooooo.......ooooooooo.\n\
+             #######################"
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("498,4 -> 498,6\n503,4 -> 50x,4").unwrap_err(),
+            ParseError::InvalidPoint(Location::new(2, 10, "503,4 -> 50x,4"))
+        );
+        assert_eq!(
+            parse("498,4 -> 498,6\n503,4 -> 502").unwrap_err(),
+            ParseError::InvalidPoint(Location::new(2, 10, "503,4 -> 502"))
+        );
+        assert_eq!(
+            parse("498,4 -> 498,6\n503,4 -> 502,5").unwrap_err(),
+            ParseError::DiagonalSegment(Location::new(2, 10, "503,4 -> 502,5"))
+        );
+        assert_eq!(
+            parse("498,4 -> 498,6\n\n503,4 -> 502,4").unwrap_err(),
+            ParseError::InvalidPoint(Location::new(2, 1, ""))
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Advent of Code 2022 Day 2: Rock Paper Scissors
+
+use std::{io::BufRead, str::FromStr};
+
+use aoc_core::{Line, Location, Solution, StreamingSolution};
+use color_eyre::Result;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid opponent shape at {0}")]
+    InvalidOpponent(Location),
+    #[error("invalid response at {0}")]
+    InvalidResponse(Location),
+    #[error("missing response at {0}")]
+    MissingResponse(Location),
+    #[error("unexpected input at {0}")]
+    UnexpectedInput(Location),
+}
+
+/// The shape played by the opponent.
+#[derive(Debug, Clone, Copy)]
+pub enum Opponent {
+    A,
+    B,
+    C,
+}
+
+/// The second column of the strategy guide, whose meaning differs between the parts.
+#[derive(Debug, Clone, Copy)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Round {
+    pub opponent: Opponent,
+    pub response: Response,
+}
+
+impl FromStr for Round {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Round::parse_line(Line { number: 1, text: s })
+    }
+}
+
+impl Round {
+    pub fn parse_line(line: Line) -> Result<Self, ParseError> {
+        let mut i = line.text.split_ascii_whitespace();
+        let f = i
+            .next()
+            .ok_or_else(|| ParseError::InvalidOpponent(line.end()))?;
+        let x = i
+            .next()
+            .ok_or_else(|| ParseError::MissingResponse(line.end()))?;
+        if let Some(rest) = i.next() {
+            return Err(ParseError::UnexpectedInput(line.locate(rest)));
+        }
+        let opponent = match f {
+            "A" => Opponent::A,
+            "B" => Opponent::B,
+            "C" => Opponent::C,
+            _ => return Err(ParseError::InvalidOpponent(line.locate(f))),
+        };
+        let response = match x {
+            "X" => Response::X,
+            "Y" => Response::Y,
+            "Z" => Response::Z,
+            _ => return Err(ParseError::InvalidResponse(line.locate(x))),
+        };
+
+        Ok(Round { opponent, response })
+    }
+
+    /// Score if the response is the shape we play.
+    pub fn score_part1(&self) -> usize {
+        match self.response {
+            Response::X => {
+                1 + match self.opponent {
+                    Opponent::A => 3,
+                    Opponent::B => 0,
+                    Opponent::C => 6,
+                }
+            }
+            Response::Y => {
+                2 + match self.opponent {
+                    Opponent::A => 6,
+                    Opponent::B => 3,
+                    Opponent::C => 0,
+                }
+            }
+            Response::Z => {
+                3 + match self.opponent {
+                    Opponent::A => 0,
+                    Opponent::B => 6,
+                    Opponent::C => 3,
+                }
+            }
+        }
+    }
+    /// Score if the response is the outcome we need.
+    pub fn score_part2(&self) -> usize {
+        match self.response {
+            Response::X => match self.opponent {
+                Opponent::A => 3,
+                Opponent::B => 1,
+                Opponent::C => 2,
+            },
+            Response::Y => match self.opponent {
+                Opponent::A => 1 + 3,
+                Opponent::B => 2 + 3,
+                Opponent::C => 3 + 3,
+            },
+            Response::Z => match self.opponent {
+                Opponent::A => 2 + 6,
+                Opponent::B => 3 + 6,
+                Opponent::C => 1 + 6,
+            },
+        }
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Round>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+impl StreamingSolution for Day2 {
+    fn solve_reader(reader: &mut dyn BufRead) -> Result<(usize, usize)> {
+        let (mut score1, mut score2) = (0, 0);
+        aoc_core::for_each_line(reader, |line| {
+            let round = Round::parse_line(line)?;
+            score1 += round.score_part1();
+            score2 += round.score_part2();
+            Ok::<_, ParseError>(())
+        })?;
+        Ok((score1, score2))
+    }
+}
+
+/// Parses one round of the strategy guide per line.
+pub fn parse(input: &str) -> Result<Vec<Round>, ParseError> {
+    aoc_core::lines(input.trim_end())
+        .map(Round::parse_line)
+        .collect()
+}
+
+pub fn solve_part1(rounds: &[Round]) -> Result<usize> {
+    Ok(rounds.iter().map(Round::score_part1).sum())
+}
+
+pub fn solve_part2(rounds: &[Round]) -> Result<usize> {
+    Ok(rounds.iter().map(Round::score_part2).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let rounds = parse(kat).unwrap();
+        assert_eq!(solve_part1(&rounds).unwrap(), 15);
+        assert_eq!(solve_part2(&rounds).unwrap(), 12);
+    }
+    #[test]
+    fn test_stream() {
+        let kat = include_str!("../fixtures/example.txt");
+        assert_eq!(Day2::solve_reader(&mut kat.as_bytes()).unwrap(), (15, 12));
+
+        let error = Day2::solve_reader(&mut "A Y\n\nB X\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ParseError>(),
+            parse("A Y\n\nB X\n").err().as_ref()
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("A Y\nD X").unwrap_err(),
+            ParseError::InvalidOpponent(Location::new(2, 1, "D X"))
+        );
+        assert_eq!(
+            parse("A Y\n\nB X").unwrap_err(),
+            ParseError::InvalidOpponent(Location::new(2, 1, ""))
+        );
+        assert_eq!(
+            parse("A Y\nB  W").unwrap_err(),
+            ParseError::InvalidResponse(Location::new(2, 4, "B  W"))
+        );
+        assert_eq!(
+            parse("C").unwrap_err(),
+            ParseError::MissingResponse(Location::new(1, 2, "C"))
+        );
+        assert_eq!(
+            parse("A Y\nB X\nC Z Z").unwrap_err(),
+            ParseError::UnexpectedInput(Location::new(3, 5, "C Z Z"))
+        );
+        assert_eq!(
+            "A Q".parse::<Round>().unwrap_err().to_string(),
+            "invalid response at line 1, column 3: \"A Q\""
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Advent of Code 2022 Day 3: Rucksack Reorganization
+
+use aoc_core::{Line, Location, Solution, StreamingSolution};
+use color_eyre::{eyre::eyre, Result};
+use std::{collections::HashSet, io::BufRead};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid item at {0}")]
+    InvalidItem(Location),
+    #[error("compartments of different size at {0}")]
+    OddLength(Location),
+    #[error("no item in both compartments at {0}")]
+    NoCommonItem(Location),
+    #[error("no badge in the group starting at {0}")]
+    NoBadge(Location),
+    #[error("incomplete group starting at {0}")]
+    IncompleteGroup(Location),
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Vec<usize>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+impl StreamingSolution for Day3 {
+    fn solve_reader(reader: &mut dyn BufRead) -> Result<(usize, usize)> {
+        let (mut sum1, mut sum2) = (0, 0);
+        // only the rucksacks of the current group are kept
+        let mut group = Vec::with_capacity(3);
+        let mut group_start = None;
+        aoc_core::for_each_line(reader, |line| {
+            let rucksack = parse_rucksack(&line)?;
+            sum1 += common_item(&rucksack).unwrap();
+            group.push(rucksack);
+
+            let start = group_start.get_or_insert_with(|| line.start());
+            if group.len() == 3 {
+                sum2 += badge(&group).ok_or_else(|| ParseError::NoBadge(start.clone()))?;
+                group.clear();
+                group_start = None;
+            }
+            Ok::<_, ParseError>(())
+        })?;
+        if let Some(start) = group_start {
+            return Err(ParseError::IncompleteGroup(start).into());
+        }
+
+        Ok((sum1, sum2))
+    }
+}
+
+// parses the priorities of the items in a rucksack with an item in both compartments
+fn parse_rucksack(line: &Line) -> Result<Vec<usize>, ParseError> {
+    let rucksack = line
+        .text
+        .char_indices()
+        .map(|(idx, x)| {
+            if x.is_ascii_lowercase() {
+                Ok(x as usize - 'a' as usize + 1)
+            } else if x.is_ascii_uppercase() {
+                Ok(x as usize - 'A' as usize + 27)
+            } else {
+                Err(ParseError::InvalidItem(
+                    line.locate(&line.text[idx..idx + x.len_utf8()]),
+                ))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if rucksack.len() % 2 != 0 {
+        return Err(ParseError::OddLength(line.end()));
+    }
+    if common_item(&rucksack).is_none() {
+        return Err(ParseError::NoCommonItem(
+            line.locate(&line.text[line.text.len() / 2..]),
+        ));
+    }
+    Ok(rucksack)
+}
+
+/// Parses each rucksack into the priorities of its items.
+///
+/// Also checks that every rucksack has an item in both compartments and every
+/// group of three has a badge, so that both parts can be solved.
+pub fn parse(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let mut rucksacks = Vec::new();
+    let mut group_start = None;
+    for line in aoc_core::lines(input.trim_end()) {
+        rucksacks.push(parse_rucksack(&line)?);
+
+        group_start.get_or_insert(line);
+        if rucksacks.len() % 3 == 0 {
+            if badge(&rucksacks[rucksacks.len() - 3..]).is_none() {
+                return Err(ParseError::NoBadge(group_start.unwrap().start()));
+            }
+            group_start = None;
+        }
+    }
+    if let Some(line) = group_start {
+        return Err(ParseError::IncompleteGroup(line.start()));
+    }
+
+    Ok(rucksacks)
+}
+
+fn common_item(rucksack: &[usize]) -> Option<usize> {
+    let len = rucksack.len();
+    let x1: HashSet<_> = rucksack[..len / 2].iter().collect();
+    rucksack[len / 2..].iter().find(|x| x1.contains(x)).copied()
+}
+
+fn badge(group: &[Vec<usize>]) -> Option<usize> {
+    let x1: HashSet<_> = group[0].iter().collect();
+    let x2: HashSet<_> = group[1].iter().filter(|x| x1.contains(x)).collect();
+    group[2].iter().find(|x| x2.contains(x)).copied()
+}
+
+/// Sum of the priorities of the item found in both compartments of each rucksack.
+pub fn solve_part1(rucksacks: &[Vec<usize>]) -> Result<usize> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(idx, x)| {
+            common_item(x)
+                .ok_or_else(|| eyre!("rucksack {} has no item in both compartments", idx + 1))
+        })
+        .sum()
+}
+
+/// Sum of the priorities of the badge shared by each group of three elves.
+pub fn solve_part2(rucksacks: &[Vec<usize>]) -> Result<usize> {
+    rucksacks
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(idx, a)| badge(a).ok_or_else(|| eyre!("group {} has no badge", idx + 1)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let rucksacks = parse(kat).unwrap();
+        assert_eq!(solve_part1(&rucksacks).unwrap(), 157);
+        assert_eq!(solve_part2(&rucksacks).unwrap(), 70);
+    }
+    #[test]
+    fn test_stream() {
+        let kat = include_str!("../fixtures/example.txt");
+        assert_eq!(Day3::solve_reader(&mut kat.as_bytes()).unwrap(), (157, 70));
+
+        for input in [
+            "abca\nabcABC\nabca",
+            "abca\nabca\nxyzx\nabca\nabca\nabca",
+            "abca\nabca\nabca\nxyzx\n",
+        ] {
+            let error = Day3::solve_reader(&mut input.as_bytes()).unwrap_err();
+            assert_eq!(
+                error.downcast_ref::<ParseError>(),
+                parse(input).err().as_ref()
+            );
+        }
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("abca\nab-cab\nabca").unwrap_err(),
+            ParseError::InvalidItem(Location::new(2, 3, "ab-cab"))
+        );
+        assert_eq!(
+            parse("abca\nabcab\nabca").unwrap_err(),
+            ParseError::OddLength(Location::new(2, 6, "abcab"))
+        );
+        assert_eq!(
+            parse("abca\nabcABC\nabca").unwrap_err(),
+            ParseError::NoCommonItem(Location::new(2, 4, "abcABC"))
+        );
+        assert_eq!(
+            parse("abca\nabca\nxyzx\nabca\nabca\nabca").unwrap_err(),
+            ParseError::NoBadge(Location::new(1, 1, "abca"))
+        );
+        assert_eq!(
+            parse("abca\nabca\nabca\nxyzx").unwrap_err(),
+            ParseError::IncompleteGroup(Location::new(4, 1, "xyzx"))
+        );
+    }
+}
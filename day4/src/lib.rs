@@ -0,0 +1,144 @@
+//! Advent of Code 2022 Day 4: Camp Cleanup
+
+use aoc_core::{Line, Location, Solution, StreamingSolution};
+use color_eyre::Result;
+use std::{io::BufRead, ops::RangeInclusive};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("missing comma between the assignments at {0}")]
+    MissingComma(Location),
+    #[error("missing dash in assignment at {0}")]
+    MissingDash(Location),
+    #[error("invalid section number at {0}")]
+    InvalidNumber(Location),
+    #[error("assignment ends before it starts at {0}")]
+    ReversedRange(Location),
+}
+
+/// The section assignments of a pair of elves.
+pub type Pair = (RangeInclusive<usize>, RangeInclusive<usize>);
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Pair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+impl StreamingSolution for Day4 {
+    fn solve_reader(reader: &mut dyn BufRead) -> Result<(usize, usize)> {
+        let (mut contained, mut overlapping) = (0, 0);
+        aoc_core::for_each_line(reader, |line| {
+            let pair = parse_pair(line)?;
+            contained += usize::from(contains(&pair));
+            overlapping += usize::from(overlaps(&pair));
+            Ok::<_, ParseError>(())
+        })?;
+        Ok((contained, overlapping))
+    }
+}
+
+/// Parses the pairs of section assignments.
+pub fn parse(input: &str) -> Result<Vec<Pair>, ParseError> {
+    aoc_core::lines(input.trim_end()).map(parse_pair).collect()
+}
+
+fn parse_pair(line: Line) -> Result<Pair, ParseError> {
+    let (a, b) = line
+        .text
+        .split_once(',')
+        .ok_or_else(|| ParseError::MissingComma(line.end()))?;
+    Ok((parse_range(line, a)?, parse_range(line, b)?))
+}
+
+// `part` is the slice of `line` holding a single assignment like `2-4`
+fn parse_range(line: Line, part: &str) -> Result<RangeInclusive<usize>, ParseError> {
+    let (start, end) = part
+        .split_once('-')
+        .ok_or_else(|| ParseError::MissingDash(line.locate(part)))?;
+    let number = |x: &str| {
+        x.parse::<usize>()
+            .map_err(|_| ParseError::InvalidNumber(line.locate(x)))
+    };
+    let (start, end) = (number(start)?, number(end)?);
+    if start > end {
+        return Err(ParseError::ReversedRange(line.locate(part)));
+    }
+    Ok(start..=end)
+}
+
+fn contains((r, s): &Pair) -> bool {
+    (r.contains(s.start()) && r.contains(s.end())) || (s.contains(r.start()) && s.contains(r.end()))
+}
+
+fn overlaps((r, s): &Pair) -> bool {
+    (r.contains(s.start()) || r.contains(s.end())) || (s.contains(r.start()) || s.contains(r.end()))
+}
+
+/// Number of pairs where one assignment fully contains the other.
+pub fn solve_part1(pairs: &[Pair]) -> Result<usize> {
+    Ok(pairs.iter().filter(|pair| contains(pair)).count())
+}
+
+/// Number of pairs whose assignments overlap at all.
+pub fn solve_part2(pairs: &[Pair]) -> Result<usize> {
+    Ok(pairs.iter().filter(|pair| overlaps(pair)).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let pairs = parse(kat).unwrap();
+        assert_eq!(solve_part1(&pairs).unwrap(), 2);
+        assert_eq!(solve_part2(&pairs).unwrap(), 4);
+    }
+    #[test]
+    fn test_stream() {
+        let kat = include_str!("../fixtures/example.txt");
+        assert_eq!(Day4::solve_reader(&mut kat.as_bytes()).unwrap(), (2, 4));
+
+        let error = Day4::solve_reader(&mut "2-4,6-8\n3-2,4-5".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ParseError>(),
+            parse("2-4,6-8\n3-2,4-5").err().as_ref()
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("2-4,6-8\n2-3 4-5").unwrap_err(),
+            ParseError::MissingComma(Location::new(2, 8, "2-3 4-5"))
+        );
+        assert_eq!(
+            parse("2-4,6-8\n2-3,45").unwrap_err(),
+            ParseError::MissingDash(Location::new(2, 5, "2-3,45"))
+        );
+        assert_eq!(
+            parse("2-4,6-8\n2-3,4-x").unwrap_err(),
+            ParseError::InvalidNumber(Location::new(2, 7, "2-3,4-x"))
+        );
+        assert_eq!(
+            parse("2-4,6-8\n3-2,4-5").unwrap_err(),
+            ParseError::ReversedRange(Location::new(2, 1, "3-2,4-5"))
+        );
+    }
+}
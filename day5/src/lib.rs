@@ -0,0 +1,264 @@
+//! Advent of Code 2022 Day 5: Supply Stacks
+
+use aoc_core::{Line, Location, Solution};
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+use std::{collections::VecDeque, str::FromStr};
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("stacks not numbered 1, 2, 3, ... at {0}")]
+    InvalidStackNumber(Location),
+    #[error("crate outside of the numbered stacks at {0}")]
+    CrateOutOfRange(Location),
+    #[error("missing the line numbering the stacks")]
+    MissingStackNumbers,
+    #[error("invalid move at {0}")]
+    InvalidMove(Location),
+    #[error("no such stack at {0}")]
+    StackOutOfRange(Location),
+}
+
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    /// Crates of each stack, bottom first.
+    pub stacks: Vec<VecDeque<char>>,
+    pub moves: Vec<Move>,
+}
+
+impl FromStr for Puzzle {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stack_lines = Vec::new();
+        let mut stack_done = false;
+        let mut stacks: Vec<VecDeque<char>> = Vec::new();
+        let mut moves: Vec<Move> = Vec::new();
+        for line in aoc_core::lines(s) {
+            if line.text.is_empty() {
+                continue;
+            }
+            if !line.text.starts_with(" 1 ") && !stack_done {
+                stack_lines.push(line);
+                continue;
+            }
+            if !stack_done {
+                stack_done = true;
+                for (idx, number) in line.text.split_ascii_whitespace().enumerate() {
+                    if number.parse() != Ok(idx + 1) {
+                        return Err(ParseError::InvalidStackNumber(line.locate(number)));
+                    }
+                    stacks.push(VecDeque::new());
+                }
+
+                for sl in stack_lines.iter() {
+                    for (idx, (offset, c)) in sl.text.char_indices().skip(1).step_by(4).enumerate()
+                    {
+                        if c.is_alphabetic() {
+                            stacks
+                                .get_mut(idx)
+                                .ok_or_else(|| {
+                                    ParseError::CrateOutOfRange(
+                                        sl.locate(&sl.text[offset..offset + c.len_utf8()]),
+                                    )
+                                })?
+                                .push_front(c);
+                        }
+                    }
+                }
+                continue;
+            }
+            moves.push(Move::parse_line(line, stacks.len())?);
+        }
+        if !stack_done {
+            return Err(ParseError::MissingStackNumbers);
+        }
+
+        Ok(Puzzle { stacks, moves })
+    }
+}
+
+impl Puzzle {
+    /// Top crates after moving them one at a time (CrateMover 9000).
+    pub fn solve_part1(mut self) -> Result<String> {
+        for (idx, m) in self.moves.iter().enumerate() {
+            debug!(amount = m.amount, from = m.from + 1, to = m.to + 1, "move");
+            // each crate goes back where it came from before the next is taken
+            if m.from == m.to {
+                continue;
+            }
+            let crates = Self::take(&mut self.stacks, idx, m)?;
+            self.stacks[m.to].extend(crates.into_iter().rev());
+        }
+        Self::top_crates(&mut self.stacks)
+    }
+    /// Top crates after moving them all at once (CrateMover 9001).
+    pub fn solve_part2(mut self) -> Result<String> {
+        for (idx, m) in self.moves.iter().enumerate() {
+            debug!(amount = m.amount, from = m.from + 1, to = m.to + 1, "move");
+            let crates = Self::take(&mut self.stacks, idx, m)?;
+            self.stacks[m.to].extend(crates);
+        }
+        Self::top_crates(&mut self.stacks)
+    }
+
+    // takes the top crates off the source stack of the move at `idx`, in the
+    // order they were stacked
+    fn take(stacks: &mut [VecDeque<char>], idx: usize, m: &Move) -> Result<VecDeque<char>> {
+        let stack = &mut stacks[m.from];
+        let rest = stack.len().checked_sub(m.amount).ok_or_else(|| {
+            eyre!(
+                "move {} takes more crates than stack {} holds",
+                idx + 1,
+                m.from + 1
+            )
+        })?;
+        Ok(stack.split_off(rest))
+    }
+
+    fn top_crates(stacks: &mut [VecDeque<char>]) -> Result<String> {
+        stacks
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, x)| {
+                x.pop_back()
+                    .ok_or_else(|| eyre!("stack {} ends up empty", idx + 1))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Move {
+    pub amount: usize,
+    /// Zero-based index of the source stack.
+    pub from: usize,
+    /// Zero-based index of the target stack.
+    pub to: usize,
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Move::parse_line(Line { number: 1, text: s }, usize::MAX)
+    }
+}
+
+impl Move {
+    /// Parses a move between `num_stacks` stacks, which are numbered from 1.
+    pub fn parse_line(line: Line, num_stacks: usize) -> Result<Self, ParseError> {
+        let regex = Regex::new(r#"^move (\d+) from (\d+) to (\d+)$"#).unwrap();
+
+        let cap = regex
+            .captures(line.text)
+            .ok_or_else(|| ParseError::InvalidMove(line.start()))?;
+        let number = |idx| {
+            let m = cap.get(idx).unwrap().as_str();
+            m.parse::<usize>()
+                .map_err(|_| ParseError::InvalidMove(line.locate(m)))
+        };
+        let stack = |idx| {
+            let n = number(idx)?;
+            if n == 0 || n > num_stacks {
+                let m = cap.get(idx).unwrap().as_str();
+                return Err(ParseError::StackOutOfRange(line.locate(m)));
+            }
+            Ok(n - 1)
+        };
+
+        Ok(Move {
+            amount: number(1)?,
+            from: stack(2)?,
+            to: stack(3)?,
+        })
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Puzzle;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim_end().parse::<Puzzle>()?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        input.clone().solve_part1()
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        input.clone().solve_part2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let puz: Puzzle = kat.parse().unwrap();
+        assert_eq!(puz.clone().solve_part1().unwrap(), String::from("CMZ"));
+        assert_eq!(puz.solve_part2().unwrap(), String::from("MCD"));
+    }
+    #[test]
+    fn test_errors() {
+        let kat = include_str!("../fixtures/example.txt");
+        let with_move = |m| format!("{}\n{}", kat.trim_end(), m);
+
+        assert_eq!(
+            kat.replace(" 3 ", " x ").parse::<Puzzle>().unwrap_err(),
+            ParseError::InvalidStackNumber(Location::new(4, 10, " 1   2   x "))
+        );
+        assert_eq!(
+            kat.replace(" 3 ", " 4 ").parse::<Puzzle>().unwrap_err(),
+            ParseError::InvalidStackNumber(Location::new(4, 10, " 1   2   4 "))
+        );
+        assert_eq!(
+            kat.replace("[P]", "[P] [Q]").parse::<Puzzle>().unwrap_err(),
+            ParseError::CrateOutOfRange(Location::new(3, 14, "[Z] [M] [P] [Q]"))
+        );
+        assert_eq!(
+            kat.replace(" 1   2   3 ", "")
+                .parse::<Puzzle>()
+                .unwrap_err(),
+            ParseError::MissingStackNumbers
+        );
+        assert_eq!(
+            with_move("move 1 from 2").parse::<Puzzle>().unwrap_err(),
+            ParseError::InvalidMove(Location::new(10, 1, "move 1 from 2"))
+        );
+        assert_eq!(
+            with_move("move 1 from 4 to 1")
+                .parse::<Puzzle>()
+                .unwrap_err(),
+            ParseError::StackOutOfRange(Location::new(10, 13, "move 1 from 4 to 1"))
+        );
+        assert_eq!(
+            "move 1 from 0 to 1".parse::<Move>().unwrap_err(),
+            ParseError::StackOutOfRange(Location::new(1, 13, "move 1 from 0 to 1"))
+        );
+
+        // more crates moved than there are, or a stack left empty
+        let puz: Puzzle = "[A]\n 1 \n\nmove 2 from 1 to 1".parse().unwrap();
+        // one at a time, the crate goes back on before the next is taken
+        assert_eq!(puz.clone().solve_part1().unwrap(), "A");
+        assert!(puz.solve_part2().is_err());
+        // checked before anything is allocated for the crates
+        let puz: Puzzle = "[A]\n 1 \n\nmove 1000000000000000000 from 1 to 1"
+            .parse()
+            .unwrap();
+        assert_eq!(puz.clone().solve_part1().unwrap(), "A");
+        assert!(puz.solve_part2().is_err());
+        let puz: Puzzle = "[A]    \n 1   2 \n\nmove 1 from 1 to 2".parse().unwrap();
+        assert!(puz.clone().solve_part1().is_err());
+        assert!(puz.solve_part2().is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Advent of Code 2022 Day 6: Tuning Trouble
+
+use std::{collections::VecDeque, io::BufRead};
+
+use aoc_core::{Location, Solution, StreamingSolution};
+use color_eyre::{eyre::eyre, Result};
+use thiserror::Error;
+
+pub mod naive;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid character in the datastream at {0}")]
+    InvalidCharacter(Location),
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+// how many characters before an invalid one are shown by the streaming solver
+const CONTEXT: usize = 40;
+
+impl StreamingSolution for Day6 {
+    /// Reads the datastream in chunks, as it's a single line that may be too
+    /// long to keep in memory. For the same reason the location of an invalid
+    /// character only shows the characters just before it.
+    fn solve_reader(reader: &mut dyn BufRead) -> Result<(usize, usize)> {
+        let (mut packet, mut message) = (Window::new(4), Window::new(14));
+        let (mut start_of_packet, mut start_of_message) = (None, None);
+        let mut context = VecDeque::with_capacity(CONTEXT);
+        // whitespace is only allowed at the end, so where it started
+        let mut trailing = None;
+        let mut offset = 0;
+
+        loop {
+            let buf = reader.fill_buf()?;
+            if buf.is_empty() {
+                break;
+            }
+            for &x in buf {
+                let location = |context: &VecDeque<u8>| Location {
+                    line: 1,
+                    column: offset + 1,
+                    text: String::from_utf8_lossy(&context.iter().copied().collect::<Vec<_>>())
+                        .into_owned(),
+                };
+                if x.is_ascii_whitespace() {
+                    // not part of the datastream, so it stays out of the windows
+                    trailing.get_or_insert_with(|| location(&context));
+                    continue;
+                } else if let Some(location) = trailing {
+                    return Err(ParseError::InvalidCharacter(location).into());
+                } else if !x.is_ascii_lowercase() {
+                    return Err(ParseError::InvalidCharacter(location(&context)).into());
+                }
+
+                offset += 1;
+                if context.len() == CONTEXT {
+                    context.pop_front();
+                }
+                context.push_back(x);
+                if start_of_packet.is_none() && packet.push(x) {
+                    start_of_packet = Some(offset);
+                }
+                if start_of_message.is_none() && message.push(x) {
+                    start_of_message = Some(offset);
+                }
+            }
+            let len = buf.len();
+            reader.consume(len);
+        }
+
+        Ok((
+            start_of_packet.ok_or_else(|| eyre!("no start-of-packet marker"))?,
+            start_of_message.ok_or_else(|| eyre!("no start-of-message marker"))?,
+        ))
+    }
+}
+
+/// Checks that the datastream consists of lowercase letters only.
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    let input = input.trim_end();
+    if let Some((idx, _)) = input.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        return Err(ParseError::InvalidCharacter(aoc_core::locate(input, idx)));
+    }
+    Ok(input.to_owned())
+}
+
+/// Number of characters read until the first start-of-packet marker.
+pub fn solve_part1(input: &str) -> Result<usize> {
+    find_marker(input, 4).ok_or_else(|| eyre!("no start-of-packet marker"))
+}
+
+/// Number of characters read until the first start-of-message marker.
+pub fn solve_part2(input: &str) -> Result<usize> {
+    find_marker(input, 14).ok_or_else(|| eyre!("no start-of-message marker"))
+}
+
+/// Number of bytes read until the last `len` bytes were all different.
+pub fn find_marker(input: &str, len: usize) -> Option<usize> {
+    let mut window = Window::new(len);
+    input
+        .bytes()
+        .position(|x| window.push(x))
+        .map(|idx| idx + 1)
+}
+
+/// The last bytes of a datastream, keeping count of how often each byte occurs
+/// in it and of how many bytes occur more than once.
+struct Window {
+    len: usize,
+    bytes: VecDeque<u8>,
+    counts: [usize; 256],
+    duplicates: usize,
+}
+
+impl Window {
+    fn new(len: usize) -> Self {
+        Window {
+            len,
+            bytes: VecDeque::with_capacity(len + 1),
+            counts: [0; 256],
+            duplicates: 0,
+        }
+    }
+
+    /// Slides the window over `x`, returning whether its `len` bytes are now
+    /// all different.
+    fn push(&mut self, x: u8) -> bool {
+        self.bytes.push_back(x);
+        self.counts[x as usize] += 1;
+        if self.counts[x as usize] == 2 {
+            self.duplicates += 1;
+        }
+        if self.bytes.len() > self.len {
+            let old = self.bytes.pop_front().unwrap() as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 1 {
+                self.duplicates -= 1;
+            }
+        }
+        self.bytes.len() == self.len && self.duplicates == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_kat() {
+        let kats = vec![
+            (include_str!("../fixtures/example1.txt"), (7, 19)),
+            (include_str!("../fixtures/example2.txt"), (5, 23)),
+            (include_str!("../fixtures/example3.txt"), (6, 23)),
+            (include_str!("../fixtures/example4.txt"), (10, 29)),
+            (include_str!("../fixtures/example5.txt"), (11, 26)),
+        ];
+
+        for (input, output) in kats {
+            let input = input.trim_end();
+            assert_eq!(solve_part1(input).unwrap(), output.0);
+            assert_eq!(solve_part2(input).unwrap(), output.1);
+        }
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("mjqj\npqm").unwrap_err(),
+            ParseError::InvalidCharacter(Location::new(1, 5, "mjqj"))
+        );
+        assert!(solve_part1("abcabc").is_err());
+    }
+    #[test]
+    fn test_stream() {
+        let kat = include_str!("../fixtures/example1.txt");
+        assert_eq!(Day6::solve_reader(&mut kat.as_bytes()).unwrap(), (7, 19));
+
+        let error = Day6::solve_reader(&mut "mjqj\npqm".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ParseError>(),
+            parse("mjqj\npqm").err().as_ref()
+        );
+        let error = Day6::solve_reader(&mut "mjqjApqm".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid character in the datastream at line 1, column 5: \"mjqj\""
+        );
+        assert!(Day6::solve_reader(&mut "abcdabcd\n".as_bytes()).is_err());
+        // a line ending doesn't complete a marker
+        assert!(Day6::solve_reader(&mut "abcdefghijklm\n".as_bytes()).is_err());
+        assert!(Day6::solve_reader(&mut "abcdefghijkl\r\n".as_bytes()).is_err());
+    }
+    proptest! {
+        #[test]
+        fn test_same_as_naive(input in "[a-z]{0,40}|[a-p]{0,200}", ending in "|\n|\r\n") {
+            prop_assert_eq!(solve_part1(&input).ok(), naive::solve_part1(&input).ok());
+            prop_assert_eq!(solve_part2(&input).ok(), naive::solve_part2(&input).ok());
+            let both = solve_part1(&input).ok().zip(solve_part2(&input).ok());
+            let file = input + &ending;
+            prop_assert_eq!(Day6::solve_reader(&mut file.as_bytes()).ok(), both);
+        }
+    }
+}
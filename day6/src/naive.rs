@@ -0,0 +1,32 @@
+//! The original solutions, checking each window on its own. They are kept as
+//! the reference the faster solutions are tested against.
+
+use color_eyre::{eyre::eyre, Result};
+use itertools::Itertools;
+use std::collections::HashSet;
+
+/// Number of characters read until the first start-of-packet marker.
+pub fn solve_part1(input: &str) -> Result<usize> {
+    Ok(input
+        .chars()
+        .tuple_windows()
+        .enumerate()
+        .find(|(_idx, (a, b, c, d))| HashSet::from([a, b, c, d]).len() == 4)
+        .ok_or_else(|| eyre!("no start-of-packet marker"))?
+        .0
+        + 4)
+}
+
+/// Number of characters read until the first start-of-message marker.
+pub fn solve_part2(input: &str) -> Result<usize> {
+    Ok(input
+        .chars()
+        .collect::<Vec<_>>()
+        .as_slice()
+        .windows(14)
+        .enumerate()
+        .find(|(_idx, chars)| HashSet::from(<[char; 14]>::try_from(*chars).unwrap()).len() == 14)
+        .ok_or_else(|| eyre!("no start-of-message marker"))?
+        .0
+        + 14)
+}
@@ -0,0 +1,287 @@
+//! Advent of Code 2022 Day 7: No Space Left On Device
+
+use aoc_core::{Location, Solution};
+use color_eyre::{eyre::eyre, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{digit1, line_ending, not_line_ending, space1},
+    combinator::{map, map_res, opt, value},
+    error::ErrorKind,
+    multi::many0,
+    sequence::{preceded, separated_pair, terminated},
+    IResult,
+};
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::debug;
+
+pub mod naive;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid terminal output at {0}")]
+    Syntax(Location),
+    #[error("unexpected output after leaving the outermost directory at {0}")]
+    TrailingInput(Location),
+    #[error("directory nested too deeply at {0}")]
+    TooDeep(Location),
+}
+
+/// Directories nested deeper than this are rejected, so that parsing them and
+/// walking the tree can't overflow the stack.
+pub const MAX_DEPTH: usize = 256;
+
+#[derive(Debug)]
+pub struct Dir {
+    pub name: String,
+    pub dirs: Vec<Dir>,
+    /// Name and size of each file directly in this directory.
+    pub files: Vec<(String, usize)>,
+}
+
+/// Parses the output of `$ ls`, skipping subdirectory entries.
+pub fn parse_ls(input: &str) -> IResult<&str, Vec<(String, usize)>> {
+    // skip $ ls
+    let (input, _) = terminated(tag("$ ls"), line_ending)(input)?;
+    map(
+        many0(alt((
+            value(
+                None,
+                terminated(preceded(tag("dir "), not_line_ending), line_ending),
+            ),
+            map(
+                terminated(
+                    separated_pair(
+                        map_res(digit1, str::parse::<usize>),
+                        space1,
+                        not_line_ending,
+                    ),
+                    line_ending,
+                ),
+                Some,
+            ),
+        ))),
+        |x| {
+            x.into_iter()
+                .flatten()
+                .map(|(size, name)| (name.to_owned(), size))
+                .collect()
+        },
+    )(input)
+}
+
+fn parse_nested(start: &str, depth: usize) -> IResult<&str, Dir> {
+    // $ cd <dirname>
+    let (input, name) = terminated(preceded(tag("$ cd "), not_line_ending), line_ending)(start)?;
+    let (input, files) = parse_ls(input)?;
+    if depth > MAX_DEPTH {
+        return Err(nom::Err::Failure(nom::error::Error::new(
+            start,
+            ErrorKind::TooLarge,
+        )));
+    }
+    let (input, dirs) = many0(|input| parse_nested(input, depth + 1))(input)?;
+    let (input, _) = opt(terminated(tag("$ cd .."), line_ending))(input)?;
+
+    Ok((
+        input,
+        Dir {
+            name: name.to_owned(),
+            dirs,
+            files,
+        },
+    ))
+}
+
+/// Parses the transcript of a `$ cd <dir>` and everything below it.
+pub fn parse_dir(input: &str) -> IResult<&str, Dir> {
+    parse_nested(input, 0)
+}
+
+impl FromStr for Dir {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let offset = |rest: &str| s.len() - rest.len();
+        let (rest, dir) = parse_dir(s).map_err(|e| match e {
+            nom::Err::Failure(e) if e.code == ErrorKind::TooLarge => {
+                ParseError::TooDeep(aoc_core::locate(s, offset(e.input)))
+            }
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                ParseError::Syntax(aoc_core::locate(s, offset(e.input)))
+            }
+            nom::Err::Incomplete(_) => ParseError::Syntax(aoc_core::locate(s, s.len())),
+        })?;
+
+        if !rest.is_empty() {
+            return Err(ParseError::TrailingInput(aoc_core::locate(s, offset(rest))));
+        }
+        Ok(dir)
+    }
+}
+
+impl Dir {
+    /// Sum of the sizes of all directories of at most 100000.
+    pub fn solve_part1(&self) -> Result<usize> {
+        Ok(self
+            .sizes()?
+            .into_iter()
+            .filter(|&size| size <= 100000)
+            .sum())
+    }
+    /// Total size of this directory, including all subdirectories.
+    pub fn size(&self) -> Result<usize> {
+        let dirs = self
+            .dirs
+            .iter()
+            .map(Dir::size)
+            .collect::<Result<Vec<_>>>()?;
+        self.total(dirs)
+    }
+    /// Total sizes of this directory and all directories below it, this one last.
+    pub fn sizes(&self) -> Result<Vec<usize>> {
+        let mut sizes = Vec::new();
+        self.collect_sizes(&mut sizes)?;
+        Ok(sizes)
+    }
+    // returns the size of this directory, reusing those of its subdirectories
+    fn collect_sizes(&self, sizes: &mut Vec<usize>) -> Result<usize> {
+        let dirs = self
+            .dirs
+            .iter()
+            .map(|d| d.collect_sizes(sizes))
+            .collect::<Result<Vec<_>>>()?;
+        let size = self.total(dirs)?;
+        debug!(name = %self.name, size, "directory");
+        sizes.push(size);
+        Ok(size)
+    }
+    // adds up the sizes of the files in this directory and of `dirs`
+    fn total(&self, dirs: Vec<usize>) -> Result<usize> {
+        self.files
+            .iter()
+            .map(|x| x.1)
+            .chain(dirs)
+            .try_fold(0usize, |total, size| total.checked_add(size))
+            .ok_or_else(|| eyre!("the size of directory {} overflows", self.name))
+    }
+    /// Size of the smallest directory to delete to free up enough space for the update.
+    pub fn solve_part2(&self) -> Result<usize> {
+        let sizes = self.sizes()?;
+        // this directory's size comes last
+        let used = *sizes.last().unwrap();
+        let unused = 70000000usize
+            .checked_sub(used)
+            .ok_or_else(|| eyre!("{} used is more than the disk holds", used))?;
+        let needed = 30000000usize
+            .checked_sub(unused)
+            .ok_or_else(|| eyre!("{} unused is already enough for the update", unused))?;
+
+        sizes
+            .into_iter()
+            .filter(|&size| size > needed)
+            .min()
+            .ok_or_else(|| eyre!("no directory is larger than the {} needed", needed))
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Dir;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.parse::<Dir>()?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        input.solve_part1()
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        input.solve_part2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+
+        let dir = Dir::from_str(kat).unwrap();
+
+        assert_eq!(dir.solve_part1().unwrap(), 95437);
+        assert_eq!(dir.solve_part2().unwrap(), 24933642);
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Dir::from_str("$ cd /\n$ dir\n").unwrap_err(),
+            ParseError::Syntax(Location::new(2, 1, "$ dir"))
+        );
+        assert_eq!(
+            Dir::from_str("$ cd /\n$ ls\n12 a\n$ rm a\n").unwrap_err(),
+            ParseError::TrailingInput(Location::new(4, 1, "$ rm a"))
+        );
+        assert_eq!(
+            Dir::from_str("$ cd /\n$ ls\n12a\n").unwrap_err(),
+            ParseError::TrailingInput(Location::new(3, 1, "12a"))
+        );
+        let deep = "$ cd a\n$ ls\n".repeat(5000);
+        assert_eq!(
+            Dir::from_str(&deep).unwrap_err(),
+            ParseError::TooDeep(Location::new(2 * MAX_DEPTH + 3, 1, "$ cd a"))
+        );
+        let deep = "$ cd a\n$ ls\n".repeat(MAX_DEPTH + 1);
+        assert!(Dir::from_str(&deep).is_ok());
+
+        // nothing needs deleting, or everything together isn't enough
+        let dir = Dir::from_str("$ cd /\n$ ls\n100 a\n").unwrap();
+        assert!(dir.solve_part2().is_err());
+        let dir = Dir::from_str("$ cd /\n$ ls\n70000001 a\n").unwrap();
+        assert!(dir.solve_part2().is_err());
+        // more than fits into a usize
+        let dir = Dir::from_str("$ cd /\n$ ls\n18446744073709551615 a\n1 b\n").unwrap();
+        assert!(dir.solve_part1().is_err());
+        assert!(dir.solve_part2().is_err());
+    }
+    fn dir() -> impl Strategy<Value = Dir> {
+        let files = || proptest::collection::vec(("[a-z]{1,8}", 1..1_000_000usize), 0..4);
+        let leaf = files().prop_map(|files| Dir {
+            name: "leaf".to_owned(),
+            dirs: Vec::new(),
+            files,
+        });
+        leaf.prop_recursive(3, 16, 4, move |inner| {
+            (files(), proptest::collection::vec(inner, 0..4)).prop_map(|(files, dirs)| Dir {
+                name: "inner".to_owned(),
+                dirs,
+                files,
+            })
+        })
+    }
+
+    // the disk has to be full enough to need deleting something, but not overfull
+    fn filesystem() -> impl Strategy<Value = Dir> {
+        (dir(), 0..20_000_000usize).prop_map(|(mut root, extra)| {
+            let filler = 40_000_000usize.saturating_sub(root.size().unwrap()) + extra;
+            root.files.push(("filler".to_owned(), filler));
+            root
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_same_as_naive(root in filesystem()) {
+            prop_assert_eq!(root.solve_part1().unwrap(), naive::solve_part1(&root).unwrap());
+            prop_assert_eq!(root.solve_part2().unwrap(), naive::solve_part2(&root).unwrap());
+        }
+    }
+}
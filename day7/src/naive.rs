@@ -0,0 +1,49 @@
+//! The original solutions, which compute the size of each directory again for
+//! every directory above it. They are kept as the reference the faster
+//! solutions are tested against.
+
+use color_eyre::{eyre::eyre, Result};
+
+use crate::Dir;
+
+/// Sum of the sizes of all directories of at most 100000.
+pub fn solve_part1(dir: &Dir) -> Result<usize> {
+    let local_size = dir.size()?;
+
+    let dirs_sizes = dir.dirs.iter().map(solve_part1).sum::<Result<usize>>()?;
+
+    if local_size <= 100000 {
+        Ok(local_size + dirs_sizes)
+    } else {
+        Ok(dirs_sizes)
+    }
+}
+
+pub fn find_smallest_subdir_above(dir: &Dir, target: usize) -> Result<Option<usize>> {
+    let subdirs = dir
+        .dirs
+        .iter()
+        .map(|x| find_smallest_subdir_above(x, target))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .min();
+
+    let size = dir.size()?;
+    Ok(match (subdirs, size > target) {
+        (None, true) => Some(size),
+        (None, false) => None,
+        (Some(x), true) => Some(x),
+        _ => unreachable!(),
+    })
+}
+
+/// Size of the smallest directory to delete to free up enough space for the update.
+pub fn solve_part2(dir: &Dir) -> Result<usize> {
+    let local_size = dir.size()?;
+    let occupied = 70000000 - local_size;
+    let needed = 30000000 - occupied;
+
+    find_smallest_subdir_above(dir, needed)?
+        .ok_or_else(|| eyre!("no directory is larger than the {} needed", needed))
+}
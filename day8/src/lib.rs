@@ -0,0 +1,176 @@
+//! Advent of Code 2022 Day 8: Treetop Tree House
+
+use aoc_core::{Location, Solution};
+use color_eyre::Result;
+use thiserror::Error;
+use tracing::debug;
+
+pub mod naive;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid tree height at {0}")]
+    InvalidHeight(Location),
+    #[error("row of a different width than the first at {0}")]
+    RaggedRow(Location),
+    #[error("no trees in input")]
+    Empty,
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Vec<Vec<usize>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+/// Parses the tree heights, indexed as `trees[row][column]`.
+pub fn parse(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let mut trees: Vec<Vec<usize>> = Vec::new();
+    for line in aoc_core::lines(input.trim_end()) {
+        let row = line
+            .text
+            .char_indices()
+            .map(|(idx, x)| {
+                x.to_digit(10).map(|x| x as usize).ok_or_else(|| {
+                    ParseError::InvalidHeight(line.locate(&line.text[idx..idx + x.len_utf8()]))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(first) = trees.first() {
+            if row.len() != first.len() {
+                let column = row.len().min(first.len());
+                let offset = line
+                    .text
+                    .char_indices()
+                    .nth(column)
+                    .map_or(line.text.len(), |x| x.0);
+                return Err(ParseError::RaggedRow(line.locate(&line.text[offset..])));
+            }
+        }
+        trees.push(row);
+    }
+    if trees.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(trees)
+}
+
+/// Number of trees visible from outside the grid.
+pub fn solve_part1(trees: &[Vec<usize>]) -> Result<usize> {
+    let (width, height) = (trees[0].len(), trees.len());
+    let mut visible = vec![vec![false; width]; height];
+
+    // a tree is visible from a side if it's taller than every tree before it
+    let mut look = |line: &mut dyn Iterator<Item = (usize, usize)>| {
+        let mut tallest = None;
+        for (row, column) in line {
+            let height = trees[row][column];
+            if tallest.is_none_or(|tallest| height > tallest) {
+                visible[row][column] = true;
+                tallest = Some(height);
+            }
+        }
+    };
+    for row in 0..height {
+        look(&mut (0..width).map(|column| (row, column)));
+        look(&mut (0..width).rev().map(|column| (row, column)));
+    }
+    for column in 0..width {
+        look(&mut (0..height).map(|row| (row, column)));
+        look(&mut (0..height).rev().map(|row| (row, column)));
+    }
+
+    Ok(visible.iter().flatten().filter(|&&x| x).count())
+}
+
+/// Highest scenic score of any tree.
+pub fn solve_part2(trees: &[Vec<usize>]) -> Result<usize> {
+    let (width, height) = (trees[0].len(), trees.len());
+    let mut scores = vec![vec![1; width]; height];
+
+    // the view ends at the closest tree at least as tall, which is found by
+    // remembering where each height was last seen
+    let mut look = |line: &mut dyn Iterator<Item = (usize, usize)>| {
+        let mut last_seen = [0; 10];
+        for (distance, (row, column)) in line.enumerate() {
+            let height = trees[row][column];
+            let blocked_at = last_seen[height.min(9)..].iter().max().unwrap();
+            scores[row][column] *= distance - blocked_at;
+            last_seen[height.min(9)] = distance;
+        }
+    };
+    for row in 0..height {
+        look(&mut (0..width).map(|column| (row, column)));
+        look(&mut (0..width).rev().map(|column| (row, column)));
+    }
+    for column in 0..width {
+        look(&mut (0..height).map(|row| (row, column)));
+        look(&mut (0..height).rev().map(|row| (row, column)));
+    }
+
+    let best = (0..height)
+        .flat_map(|row| (0..width).map(move |column| (row, column)))
+        .max_by_key(|&(row, column)| scores[row][column]);
+    if let Some((row, column)) = best {
+        debug!(row, column, score = scores[row][column], "best tree");
+    }
+    Ok(best.map_or(0, |(row, column)| scores[row][column]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+
+        let trees = parse(kat).unwrap();
+        assert_eq!(solve_part1(&trees).unwrap(), 21);
+        assert_eq!(solve_part2(&trees).unwrap(), 8);
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("303\n2x5\n").unwrap_err(),
+            ParseError::InvalidHeight(Location::new(2, 2, "2x5"))
+        );
+        assert_eq!(
+            parse("303\n25\n").unwrap_err(),
+            ParseError::RaggedRow(Location::new(2, 3, "25"))
+        );
+        assert_eq!(
+            parse("303\n2551\n").unwrap_err(),
+            ParseError::RaggedRow(Location::new(2, 4, "2551"))
+        );
+        assert_eq!(parse("\n").unwrap_err(), ParseError::Empty);
+    }
+    fn square_grid() -> impl Strategy<Value = Vec<Vec<usize>>> {
+        (1..12usize).prop_flat_map(|size| {
+            proptest::collection::vec(proptest::collection::vec(0..10usize, size), size)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_same_as_naive(trees in square_grid()) {
+            prop_assert_eq!(solve_part1(&trees).unwrap(), naive::solve_part1(&trees).unwrap());
+            prop_assert_eq!(solve_part2(&trees).unwrap(), naive::solve_part2(&trees).unwrap());
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Advent of Code 2022 Day 9: Rope Bridge
+
+use std::{collections::HashSet, iter};
+
+use aoc_core::{Line, Location, Solution};
+use color_eyre::Result;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseError {
+    #[error("invalid direction at {0}")]
+    InvalidDirection(Location),
+    #[error("missing number of steps at {0}")]
+    MissingSteps(Location),
+    #[error("invalid number of steps at {0}")]
+    InvalidSteps(Location),
+    #[error("unexpected input at {0}")]
+    UnexpectedInput(Location),
+}
+
+/// The x and y coordinates of a knot, with y growing upwards.
+pub type Pos = (isize, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    /// How a single step in this direction moves the head.
+    pub fn delta(self) -> Pos {
+        match self {
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+        }
+    }
+}
+
+/// A motion of the head, like `R 4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    pub direction: Direction,
+    pub steps: usize,
+}
+
+impl Motion {
+    pub fn parse_line(line: Line) -> Result<Self, ParseError> {
+        let mut words = line.text.split_ascii_whitespace();
+        let direction = match words.next() {
+            Some("L") => Direction::Left,
+            Some("R") => Direction::Right,
+            Some("U") => Direction::Up,
+            Some("D") => Direction::Down,
+            Some(other) => return Err(ParseError::InvalidDirection(line.locate(other))),
+            None => return Err(ParseError::InvalidDirection(line.start())),
+        };
+        let steps = words
+            .next()
+            .ok_or_else(|| ParseError::MissingSteps(line.end()))?;
+        let steps = steps
+            .parse()
+            .map_err(|_| ParseError::InvalidSteps(line.locate(steps)))?;
+        if let Some(rest) = words.next() {
+            return Err(ParseError::UnexpectedInput(line.locate(rest)));
+        }
+        Ok(Motion { direction, steps })
+    }
+}
+
+/// A rope whose knots each follow the one before, the first being the head.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rope {
+    knots: Vec<Pos>,
+}
+
+impl Rope {
+    /// A rope of `len` knots, all at the start. There must be at least one.
+    pub fn new(len: usize) -> Self {
+        assert!(len > 0, "a rope has at least one knot");
+        Rope {
+            knots: vec![(0, 0); len],
+        }
+    }
+
+    /// The positions of the knots, head first.
+    pub fn knots(&self) -> &[Pos] {
+        &self.knots
+    }
+
+    pub fn tail(&self) -> Pos {
+        *self.knots.last().unwrap()
+    }
+
+    /// Moves the head a single step, pulling along the knots behind it.
+    pub fn step(&mut self, direction: Direction) {
+        let (dx, dy) = direction.delta();
+        self.knots[0].0 += dx;
+        self.knots[0].1 += dy;
+
+        for idx in 1..self.knots.len() {
+            let (x, y) = self.knots[idx - 1];
+            let knot = &mut self.knots[idx];
+            if (x - knot.0).abs() <= 1 && (y - knot.1).abs() <= 1 {
+                // the knots further back don't move either
+                break;
+            }
+            knot.0 += (x - knot.0).signum();
+            knot.1 += (y - knot.1).signum();
+        }
+    }
+
+    /// Draws the rope like the puzzle does, within the smallest area that
+    /// contains all knots and the start: `H` for the head, then `T` for the
+    /// tail of a rope of two knots or the number of each knot, and `s` for the
+    /// start. Knots closer to the head cover those behind them.
+    pub fn render(&self) -> String {
+        let xs = self.knots.iter().map(|pos| pos.0).chain([0]);
+        let ys = self.knots.iter().map(|pos| pos.1).chain([0]);
+        let (min_x, max_x) = (xs.clone().min().unwrap(), xs.max().unwrap());
+        let (min_y, max_y) = (ys.clone().min().unwrap(), ys.max().unwrap());
+
+        let mut rows = Vec::new();
+        for y in (min_y..=max_y).rev() {
+            let row: String = (min_x..=max_x)
+                .map(|x| match self.knots.iter().position(|&pos| pos == (x, y)) {
+                    Some(0) => 'H',
+                    Some(_) if self.knots.len() == 2 => 'T',
+                    Some(idx) => char::from_digit(idx as u32 % 10, 10).unwrap(),
+                    None if (x, y) == (0, 0) => 's',
+                    None => '.',
+                })
+                .collect();
+            rows.push(row);
+        }
+        rows.join("\n")
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Motion>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        solve_part1(input)
+    }
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        solve_part2(input)
+    }
+}
+
+/// Parses one motion of the head per line.
+pub fn parse(input: &str) -> Result<Vec<Motion>, ParseError> {
+    aoc_core::lines(input.trim_end())
+        .map(Motion::parse_line)
+        .collect()
+}
+
+fn steps(motions: &[Motion]) -> impl Iterator<Item = Direction> + '_ {
+    motions
+        .iter()
+        .flat_map(|motion| iter::repeat_n(motion.direction, motion.steps))
+}
+
+/// The rope of `len` knots after each single step of the motions, e.g. to
+/// render it.
+pub fn simulate(motions: &[Motion], len: usize) -> impl Iterator<Item = Rope> + '_ {
+    let mut rope = Rope::new(len);
+    steps(motions).map(move |direction| {
+        rope.step(direction);
+        rope.clone()
+    })
+}
+
+/// Number of positions visited by the tail of a rope of `len` knots.
+pub fn count_tail_positions(motions: &[Motion], len: usize) -> usize {
+    let mut rope = Rope::new(len);
+    let mut visited = HashSet::from([rope.tail()]);
+    for direction in steps(motions) {
+        rope.step(direction);
+        visited.insert(rope.tail());
+    }
+    visited.len()
+}
+
+/// Number of positions visited by the tail of a rope of two knots.
+pub fn solve_part1(motions: &[Motion]) -> Result<usize> {
+    Ok(count_tail_positions(motions, 2))
+}
+
+/// Number of positions visited by the tail of a rope of ten knots.
+pub fn solve_part2(motions: &[Motion]) -> Result<usize> {
+    Ok(count_tail_positions(motions, 10))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kat() {
+        let kat = include_str!("../fixtures/example.txt");
+        let motions = parse(kat).unwrap();
+        assert_eq!(
+            solve_part1(&motions).unwrap().to_string(),
+            include_str!("../fixtures/answer1.txt").trim_end()
+        );
+        assert_eq!(
+            solve_part2(&motions).unwrap().to_string(),
+            include_str!("../fixtures/answer2.txt").trim_end()
+        );
+
+        let larger = parse(include_str!("../fixtures/example2.txt")).unwrap();
+        assert_eq!(solve_part2(&larger).unwrap(), 36);
+    }
+    #[test]
+    fn test_render() {
+        let motions = parse(include_str!("../fixtures/example.txt")).unwrap();
+
+        let ropes: Vec<_> = simulate(&motions, 2).collect();
+        assert_eq!(ropes.len(), 24);
+        assert_eq!(ropes[3].render(), "s..TH");
+        assert_eq!(ropes[7].render(), "....H\n....T\n.....\n.....\ns....");
+        assert_eq!(ropes[23].knots(), [(2, 2), (1, 2)]);
+
+        let rope = simulate(&motions, 10).nth(7).unwrap();
+        assert_eq!(rope.render(), "....H\n....1\n..432\n.5...\n6....");
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("R 4\nX 4").unwrap_err(),
+            ParseError::InvalidDirection(Location::new(2, 1, "X 4"))
+        );
+        assert_eq!(
+            parse("R 4\nU").unwrap_err(),
+            ParseError::MissingSteps(Location::new(2, 2, "U"))
+        );
+        assert_eq!(
+            parse("R 4\nU -2").unwrap_err(),
+            ParseError::InvalidSteps(Location::new(2, 3, "U -2"))
+        );
+        assert_eq!(
+            parse("R 4\nU 2 3").unwrap_err(),
+            ParseError::UnexpectedInput(Location::new(2, 5, "U 2 3"))
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Fuzzing of the input parsers that runs on stable Rust.
+//!
+//! Rather than being guided by coverage, the inputs are made by randomly
+//! mutating a corpus of valid inputs, with a bias towards the tokens that the
+//! parsers look for. Every parser must return either `Ok` or `Err` for any
+//! input, so a panic is a bug.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use aoc_core::Solution;
+
+/// The parsers of one day.
+#[derive(Debug, Clone, Copy)]
+pub struct Target {
+    pub day: u8,
+    parse: fn(&str),
+}
+
+impl Target {
+    pub fn name(&self) -> String {
+        format!("day{}", self.day)
+    }
+
+    /// Runs the parsers on `input`, returning the message if one of them panicked.
+    pub fn run(&self, input: &[u8]) -> Result<(), String> {
+        // the runner only ever passes valid UTF-8 to the parsers
+        let input = String::from_utf8_lossy(input);
+        panic::catch_unwind(AssertUnwindSafe(|| (self.parse)(&input))).map_err(|payload| {
+            if let Some(message) = payload.downcast_ref::<&str>() {
+                message.to_string()
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.clone()
+            } else {
+                "unknown panic".to_owned()
+            }
+        })
+    }
+}
+
+fn parse<S: Solution>(input: &str) {
+    let _ = S::parse(input);
+}
+
+pub fn targets() -> Vec<Target> {
+    vec![
+        Target {
+            day: 1,
+            parse: parse::<day1::Day1>,
+        },
+        Target {
+            day: 2,
+            parse: |input| {
+                parse::<day2::Day2>(input);
+                let _ = input.parse::<day2::Round>();
+            },
+        },
+        Target {
+            day: 3,
+            parse: parse::<day3::Day3>,
+        },
+        Target {
+            day: 4,
+            parse: parse::<day4::Day4>,
+        },
+        Target {
+            day: 5,
+            parse: |input| {
+                parse::<day5::Day5>(input);
+                let _ = input.parse::<day5::Puzzle>();
+                let _ = input.parse::<day5::Move>();
+            },
+        },
+        Target {
+            day: 6,
+            parse: parse::<day6::Day6>,
+        },
+        Target {
+            day: 7,
+            parse: |input| {
+                parse::<day7::Day7>(input);
+                let _ = day7::parse_dir(input);
+                // the sizes are only added up once solving
+                if let Ok(dir) = input.parse::<day7::Dir>() {
+                    let _ = dir.solve_part1();
+                    let _ = dir.solve_part2();
+                }
+            },
+        },
+        Target {
+            day: 8,
+            parse: parse::<day8::Day8>,
+        },
+        Target {
+            day: 9,
+            parse: parse::<day9::Day9>,
+        },
+        Target {
+            day: 10,
+            parse: parse::<day10::Day10>,
+        },
+        Target {
+            day: 11,
+            parse: parse::<day11::Day11>,
+        },
+        Target {
+            day: 12,
+            parse: parse::<day12::Day12>,
+        },
+        Target {
+            day: 13,
+            parse: parse::<day13::Day13>,
+        },
+        Target {
+            day: 14,
+            parse: parse::<day14::Day14>,
+        },
+    ]
+}
+
+pub fn get(name: &str) -> Option<Target> {
+    targets().into_iter().find(|target| target.name() == name)
+}
+
+pub fn workspace_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("fuzz crate lives inside the workspace")
+}
+
+fn read_dir(dir: &Path) -> Vec<Vec<u8>> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    paths.sort();
+    paths
+        .into_iter()
+        .filter_map(|path| std::fs::read(path).ok())
+        .collect()
+}
+
+/// The examples from `dayN/fixtures`, followed by the inputs kept in
+/// `fuzz/corpus/dayN`, e.g. those that once crashed a parser.
+pub fn corpus(target: &Target) -> Vec<Vec<u8>> {
+    let mut corpus = read_dir(&workspace_dir().join(target.name()).join("fixtures"));
+    corpus.extend(read_dir(
+        &workspace_dir()
+            .join("fuzz")
+            .join("corpus")
+            .join(target.name()),
+    ));
+    corpus
+}
+
+/// Where crashing inputs of `target` are saved.
+pub fn artifact_path(target: &Target, input: &[u8]) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    workspace_dir()
+        .join("fuzz")
+        .join("artifacts")
+        .join(target.name())
+        .join(format!("crash-{:016x}", hasher.finish()))
+}
+
+/// A xorshift generator, so that a run can be repeated from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // the state must never be zero
+        Rng(seed ^ 0x9e37_79b9_7f4a_7c15 | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`, which must not be empty.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+// bits of input that mean something to at least one of the parsers
+const TOKENS: &[&str] = &[
+    "\n",
+    "\n\n",
+    "\r\n",
+    " ",
+    "-",
+    ",",
+    "[",
+    "]",
+    " 1 ",
+    "$ cd ",
+    "$ cd ..",
+    "$ ls",
+    "dir ",
+    "move ",
+    " from ",
+    " to ",
+    "noop",
+    "addx ",
+    "A X",
+    "R 4",
+    "Monkey 0:\n",
+    "  Starting items: ",
+    "  Operation: new = old * old\n",
+    "  Test: divisible by ",
+    "    If true: throw to monkey 1\n",
+    "SabE",
+    "[[]]",
+    " -> ",
+    "0",
+    "1",
+    "9",
+    "-1",
+    "18446744073709551615",
+    "99999999999999999999",
+    "é",
+];
+
+/// Applies a few random mutations to `input`, borrowing parts of `other`.
+pub fn mutate(input: &mut Vec<u8>, other: &[u8], rng: &mut Rng, max_len: usize) {
+    for _ in 0..=rng.below(4) {
+        let pos = rng.below(input.len() + 1);
+        match rng.below(5) {
+            // overwrite a byte
+            0 if pos < input.len() => input[pos] = rng.next_u64() as u8,
+            // remove a few bytes
+            1 if pos < input.len() => {
+                let end = input.len().min(pos + 1 + rng.below(16));
+                input.drain(pos..end);
+            }
+            // repeat a few bytes
+            2 if pos < input.len() => {
+                let end = input.len().min(pos + 1 + rng.below(64));
+                let copy = input[pos..end].to_vec();
+                let at = rng.below(input.len() + 1);
+                input.splice(at..at, copy);
+            }
+            // insert a part of another input
+            3 if !other.is_empty() => {
+                let start = rng.below(other.len());
+                let end = other.len().min(start + 1 + rng.below(64));
+                input.splice(pos..pos, other[start..end].iter().copied());
+            }
+            _ => {
+                let token = TOKENS[rng.below(TOKENS.len())].bytes();
+                input.splice(pos..pos, token);
+            }
+        }
+    }
+    input.truncate(max_len);
+}
+
+/// An input that made a parser panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crash {
+    pub input: Vec<u8>,
+    pub message: String,
+}
+
+/// Runs `target` on the corpus and then on `runs` mutations of it, stopping at
+/// the first panic.
+pub fn fuzz(
+    target: &Target,
+    corpus: &[Vec<u8>],
+    runs: usize,
+    seed: u64,
+    max_len: usize,
+) -> Option<Crash> {
+    let check = |input: &[u8]| {
+        target.run(input).err().map(|message| Crash {
+            input: input.to_vec(),
+            message,
+        })
+    };
+
+    if let Some(crash) = corpus.iter().find_map(|input| check(input)) {
+        return Some(crash);
+    }
+    if corpus.is_empty() {
+        return None;
+    }
+
+    let mut rng = Rng::new(seed);
+    for _ in 0..runs {
+        let mut input = corpus[rng.below(corpus.len())].clone();
+        let other = &corpus[rng.below(corpus.len())];
+        mutate(&mut input, other, &mut rng, max_len);
+        if let Some(crash) = check(&input) {
+            return Some(crash);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corpus() {
+        for target in targets() {
+            let corpus = corpus(&target);
+            assert!(!corpus.is_empty(), "no corpus for {}", target.name());
+            assert_eq!(fuzz(&target, &corpus, 0, 0, 4096), None);
+        }
+    }
+
+    // a short run with a fixed seed, the long runs are left to `cargo run -p fuzz`
+    #[test]
+    fn test_fuzz() {
+        for target in targets() {
+            assert_eq!(fuzz(&target, &corpus(&target), 200, 2022, 1024), None);
+        }
+    }
+
+    #[test]
+    fn test_mutate() {
+        let mut rng = Rng::new(7);
+        let mut input = b"1000\n2000\n".to_vec();
+        let mut again = input.clone();
+        mutate(&mut input, b"3000", &mut rng, 8);
+        mutate(&mut again, b"3000", &mut Rng::new(7), 8);
+        assert_eq!(input, again);
+        assert!(input.len() <= 8);
+    }
+}
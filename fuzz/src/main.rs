@@ -0,0 +1,76 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::{eyre::eyre, Result};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "fuzz", about = "Fuzzes the input parsers with mutated inputs")]
+struct Args {
+    /// Days to fuzz, e.g. day7; defaults to all
+    targets: Vec<String>,
+    /// Number of mutated inputs to try for each day
+    #[structopt(short, long, default_value = "100000")]
+    runs: usize,
+    /// Seed of the mutations, to repeat an earlier run; random by default
+    #[structopt(short, long)]
+    seed: Option<u64>,
+    /// Maximum length of the mutated inputs
+    #[structopt(long, default_value = "4096")]
+    max_len: usize,
+    /// Only run the parsers on these files, e.g. to reproduce a crash
+    #[structopt(long, parse(from_os_str))]
+    replay: Vec<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let targets = if args.targets.is_empty() {
+        fuzz::targets()
+    } else {
+        args.targets
+            .iter()
+            .map(|name| fuzz::get(name).ok_or_else(|| eyre!("no fuzz target {}", name)))
+            .collect::<Result<_>>()?
+    };
+    let seed = match args.seed {
+        Some(seed) => seed,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64,
+    };
+
+    for target in targets {
+        if !args.replay.is_empty() {
+            for path in &args.replay {
+                let outcome = match target.run(&std::fs::read(path)?) {
+                    Ok(()) => "ok".to_owned(),
+                    Err(message) => format!("PANIC: {}", message),
+                };
+                println!("{} {}: {}", target.name(), path.display(), outcome);
+            }
+            continue;
+        }
+
+        println!(
+            "Fuzzing {} with {} inputs, seed {}",
+            target.name(),
+            args.runs,
+            seed
+        );
+        let corpus = fuzz::corpus(&target);
+        if let Some(crash) = fuzz::fuzz(&target, &corpus, args.runs, seed, args.max_len) {
+            let path = fuzz::artifact_path(&target, &crash.input);
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(&path, &crash.input)?;
+            return Err(eyre!(
+                "{} panicked: {}\ninput saved to {}",
+                target.name(),
+                crash.message,
+                path.display()
+            ));
+        }
+    }
+
+    Ok(())
+}